@@ -0,0 +1,35 @@
+//! Compare the box-blur approximation with the separable Gaussian kernel at increasing blur widths.
+//!
+//! Run with `cargo bench --bench gaussian_blur`.
+
+use chromatic::RgbAlpha;
+use ndarray::Array2;
+use photo::{BorderMode, Filter};
+use std::{hint::black_box, time::Instant};
+
+/// Number of timed runs of each method, of which the fastest is reported.
+const RUNS: usize = 3;
+
+fn main() {
+    let image = Array2::from_shape_fn((512, 512), |(row, col)| {
+        let shade = ((row * 7 + col * 13) % 256) as f32 / 255.0;
+        RgbAlpha::new(shade, 1.0 - shade, 0.5, 1.0)
+    });
+
+    let time = |f: &dyn Fn() -> Array2<RgbAlpha<f32>>| {
+        let mut best = f64::INFINITY;
+        for _ in 0..RUNS {
+            let start = Instant::now();
+            black_box(f());
+            best = best.min(start.elapsed().as_secs_f64());
+        }
+        best * 1e3
+    };
+
+    println!("{:>6} {:>14} {:>14}", "sigma", "gaussian (ms)", "fast (ms)");
+    for sigma in [2.0, 8.0, 32.0] {
+        let exact = time(&|| image.gaussian_blur(sigma, BorderMode::Clamp));
+        let fast = time(&|| image.fast_gaussian_blur(sigma, BorderMode::Clamp));
+        println!("{sigma:>6} {exact:>14.2} {fast:>14.2}");
+    }
+}
@@ -0,0 +1,37 @@
+//! Compare sequential and parallel unique tile counting on a large image.
+//!
+//! Run with `cargo bench --features rayon --bench unique_tiles`.
+
+use chromatic::Rgb;
+use ndarray::Array2;
+use photo::Tiles;
+use std::{hint::black_box, time::Instant};
+
+/// Number of timed runs of each method, of which the fastest is reported.
+const RUNS: usize = 5;
+
+fn main() {
+    // A 2048 x 2048 image of 8 x 8 tiles, drawn from a few hundred distinct patterns
+    let image = Array2::from_shape_fn((2048, 2048), |(row, col)| {
+        let pattern = ((row / 8) * 7 + (col / 8) * 13) % 311;
+        let shade = ((pattern * 31 + (row % 8) * 8 + col % 8) % 256) as f32 / 255.0;
+        Rgb::new(shade, 1.0 - shade, (pattern % 17) as f32 / 16.0)
+    });
+    let tile_size = [8, 8];
+
+    let time = |name: &str, f: &dyn Fn() -> usize| {
+        let mut best = f64::INFINITY;
+        let mut unique = 0;
+        for _ in 0..RUNS {
+            let start = Instant::now();
+            unique = black_box(f());
+            best = best.min(start.elapsed().as_secs_f64());
+        }
+        println!("{name:>18}: {:8.2} ms ({unique} unique tiles)", best * 1e3);
+        unique
+    };
+
+    let sequential = time("unique_tiles", &|| image.unique_tiles(tile_size).len());
+    let parallel = time("par_unique_tiles", &|| image.par_unique_tiles(tile_size).len());
+    assert_eq!(sequential, parallel);
+}
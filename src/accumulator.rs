@@ -0,0 +1,114 @@
+use ndarray::Array2;
+use num_traits::Float;
+
+use crate::Components;
+
+/// Buffer of weighted colour contributions, for scatter-based rendering.
+///
+/// Samples are splatted into the buffer at fractional `[row, column]` positions, where pixel centres lie on integer
+/// coordinates, and the accumulated colours are normalised by their total weight when resolved.
+#[derive(Debug, Clone)]
+pub struct Accumulator<T, const N: usize> {
+    /// Weighted sum of the components splatted into each pixel.
+    sums: Array2<[T; N]>,
+    /// Total weight splatted into each pixel.
+    weights: Array2<T>,
+}
+
+impl<T, const N: usize> Accumulator<T, N>
+where
+    T: Float + Send + Sync,
+{
+    /// Create an empty accumulator of the given `[height, width]`.
+    pub fn new(size: [usize; 2]) -> Self {
+        Self {
+            sums: Array2::from_elem((size[0], size[1]), [T::zero(); N]),
+            weights: Array2::from_elem((size[0], size[1]), T::zero()),
+        }
+    }
+
+    /// Distribute a weighted sample bilinearly between the four pixels surrounding `coords`.
+    ///
+    /// Contributions falling outside the buffer are discarded.
+    pub fn splat<C: Components<T, N>>(&mut self, coords: [T; 2], colour: &C, weight: T) {
+        let (h, w) = self.weights.dim();
+        let (Some(r0), Some(c0)) = (coords[0].floor().to_isize(), coords[1].floor().to_isize()) else {
+            return;
+        };
+        let fr = coords[0] - coords[0].floor();
+        let fc = coords[1] - coords[1].floor();
+        let components = colour.components();
+
+        let corners = [
+            (r0, c0, (T::one() - fr) * (T::one() - fc)),
+            (r0, c0 + 1, (T::one() - fr) * fc),
+            (r0 + 1, c0, fr * (T::one() - fc)),
+            (r0 + 1, c0 + 1, fr * fc),
+        ];
+        for (r, c, share) in corners {
+            if r < 0 || c < 0 || r as usize >= h || c as usize >= w || share <= T::zero() {
+                continue;
+            }
+            let index = [r as usize, c as usize];
+            let contribution = weight * share;
+            for (sum, &value) in self.sums[index].iter_mut().zip(components.iter()) {
+                *sum = *sum + value * contribution;
+            }
+            self.weights[index] = self.weights[index] + contribution;
+        }
+    }
+
+    /// Total weight accumulated in each pixel.
+    pub fn weights(&self) -> &Array2<T> {
+        &self.weights
+    }
+
+    /// Normalise the accumulated colours by their weights.
+    ///
+    /// Pixels which received no weight resolve to zero.
+    pub fn resolve<C: Components<T, N>>(&self) -> Array2<C> {
+        Array2::from_shape_fn(self.weights.dim(), |index| {
+            let weight = self.weights[index];
+            if weight == T::zero() {
+                C::from_components([T::zero(); N])
+            } else {
+                C::from_components(self.sums[index].map(|sum| sum / weight))
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chromatic::{Grey, RgbAlpha};
+
+    use super::Accumulator;
+    use crate::Components;
+
+    #[test]
+    fn splat_distributes_weight_bilinearly() {
+        let mut buffer = Accumulator::<f32, 1>::new([3, 3]);
+        buffer.splat([0.25, 1.5], &Grey::new(1.0), 2.0);
+
+        let weights = buffer.weights();
+        assert_eq!(
+            [weights[[0, 1]], weights[[0, 2]], weights[[1, 1]], weights[[1, 2]]],
+            [0.75, 0.75, 0.25, 0.25]
+        );
+        assert_eq!(weights.sum(), 2.0);
+
+        buffer.splat([-0.5, 2.5], &Grey::new(1.0), 1.0);
+        assert_eq!(buffer.weights().sum(), 2.25);
+    }
+
+    #[test]
+    fn resolve_divides_colour_by_weight() {
+        let mut buffer = Accumulator::<f32, 4>::new([1, 2]);
+        buffer.splat([0.0, 0.0], &RgbAlpha::new(1.0, 0.0, 0.0, 1.0), 3.0);
+        buffer.splat([0.0, 0.0], &RgbAlpha::new(0.0, 0.0, 1.0, 0.5), 1.0);
+
+        let image = buffer.resolve::<RgbAlpha<f32>>();
+        assert_eq!(image[[0, 0]].components(), [0.75, 0.0, 0.25, 0.875]);
+        assert_eq!(image[[0, 1]].components(), [0.0; 4]);
+    }
+}
@@ -0,0 +1,193 @@
+use ndarray::{Array2, Zip};
+use num_traits::Float;
+
+use crate::{
+    Adjust, Components, GamutMapping, ImageError,
+    components::{cast, colour_count, luma, mean},
+};
+
+impl<C, T, const N: usize> Adjust<C, T, N> for Array2<C>
+where
+    C: Components<T, N> + Copy,
+    T: Float + Send + Sync,
+{
+    fn apply_gamma(&mut self, gamma: T) {
+        if gamma == T::one() {
+            return;
+        }
+
+        let exponent = gamma.recip();
+        self.mapv_inplace(|pixel| {
+            let mut components = pixel.components();
+            for value in components.iter_mut().take(colour_count(N)) {
+                *value = value.powf(exponent).max(T::zero()).min(T::one());
+            }
+            C::from_components(components)
+        });
+    }
+
+    fn adjust(&mut self, brightness: T, contrast: T, saturation: T) {
+        if brightness == T::zero() && contrast == T::one() && saturation == T::one() {
+            return;
+        }
+
+        let half = cast::<T, _>(0.5);
+        self.mapv_inplace(|pixel| {
+            let mut components = pixel.components();
+            let grey = luma(&components);
+            for value in components.iter_mut().take(colour_count(N)) {
+                let saturated = grey + (*value - grey) * saturation;
+                let contrasted = half + (saturated - half) * contrast;
+                *value = (contrasted + brightness).max(T::zero()).min(T::one());
+            }
+            C::from_components(components)
+        });
+    }
+
+    fn flat_field_correct(&mut self, flat: &Self) -> Result<(), ImageError> {
+        if self.dim() != flat.dim() {
+            return Err(ImageError::DimensionMismatch);
+        }
+        if flat.is_empty() {
+            return Ok(());
+        }
+
+        let reference = mean(flat.iter());
+        Zip::from(self).and(flat).for_each(|pixel, flat| {
+            let mut components = pixel.components();
+            let flat = flat.components();
+            for n in 0..colour_count(N) {
+                if flat[n] > T::zero() {
+                    components[n] = components[n] * reference[n] / flat[n];
+                }
+            }
+            *pixel = C::from_components(components);
+        });
+        Ok(())
+    }
+
+    fn gamut_map(&mut self, method: GamutMapping) {
+        self.mapv_inplace(|pixel| C::from_components(map_into_gamut(pixel.components(), method)));
+    }
+}
+
+/// Helper function to bring colour components outside `[0, 1]` into range by the given method.
+fn map_into_gamut<T: Float, const N: usize>(mut components: [T; N], method: GamutMapping) -> [T; N] {
+    let colours = colour_count(N);
+    let (lo, hi) = components[..colours]
+        .iter()
+        .fold((T::infinity(), T::neg_infinity()), |(lo, hi), &c| (lo.min(c), hi.max(c)));
+    if lo >= T::zero() && hi <= T::one() {
+        return components;
+    }
+
+    // Find the largest fraction of the chroma which keeps every component within range
+    let grey = luma(&components).max(T::zero()).min(T::one());
+    let scale = match method {
+        GamutMapping::Clip => None,
+        GamutMapping::Desaturate => {
+            let below = if lo < T::zero() { grey / (grey - lo) } else { T::one() };
+            let above = if hi > T::one() {
+                (T::one() - grey) / (hi - grey)
+            } else {
+                T::one()
+            };
+            Some(below.min(above))
+        }
+    };
+    for value in components.iter_mut().take(colours) {
+        if let Some(scale) = scale {
+            *value = grey + (*value - grey) * scale;
+        }
+        *value = value.max(T::zero()).min(T::one());
+    }
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use chromatic::{Rgb, RgbAlpha};
+    use ndarray::Array2;
+
+    use super::map_into_gamut;
+    use crate::{Adjust, Components, GamutMapping, ImageError, components::luma};
+
+    #[test]
+    fn flat_field_correction_removes_vignetting() {
+        let falloff = |col: usize| 1.0 - 0.1 * col as f32;
+        let flat = Array2::from_shape_fn((2, 5), |(_, col)| Rgb::new(0.8 * falloff(col), 0.8 * falloff(col), 0.8));
+        let mut image = Array2::from_shape_fn((2, 5), |(_, col)| Rgb::new(0.5 * falloff(col), 0.4 * falloff(col), 0.3));
+
+        image.flat_field_correct(&flat).unwrap();
+        let first = image[[0, 0]].components();
+        for pixel in &image {
+            assert!(pixel.components().iter().zip(first).all(|(a, b)| (a - b).abs() < 1e-6));
+        }
+        assert!((first[2] - 0.3).abs() < 1e-6);
+
+        let mut unchanged = image.clone();
+        let small = Array2::from_elem((2, 4), Rgb::new(0.8, 0.8, 0.8));
+        assert!(matches!(
+            unchanged.flat_field_correct(&small),
+            Err(ImageError::DimensionMismatch)
+        ));
+        assert!(unchanged.iter().zip(&image).all(|(a, b)| a.components() == b.components()));
+    }
+
+    #[test]
+    fn clipping_clamps_each_component() {
+        assert_eq!(map_into_gamut([1.5, 0.5, -0.25], GamutMapping::Clip), [1.0, 0.5, 0.0]);
+        assert_eq!(
+            map_into_gamut([0.2, 0.5, 0.75, 0.5], GamutMapping::Clip),
+            [0.2, 0.5, 0.75, 0.5]
+        );
+    }
+
+    #[test]
+    fn desaturating_keeps_luminance_and_hue_order() {
+        let colour = [1.4_f64, 0.6, 0.2];
+        let mapped = map_into_gamut(colour, GamutMapping::Desaturate);
+        assert!(mapped.iter().all(|&c| (0.0..=1.0).contains(&c)));
+        assert!((mapped[0] - 1.0).abs() < 1e-12);
+        assert!((luma(&mapped) - luma(&colour)).abs() < 1e-12);
+        assert!(mapped[0] > mapped[1] && mapped[1] > mapped[2]);
+    }
+
+    #[test]
+    fn overexposed_colours_desaturate_to_white() {
+        let mapped = map_into_gamut([1.8, 1.5, 1.2, 0.5], GamutMapping::Desaturate);
+        assert_eq!(mapped, [1.0, 1.0, 1.0, 0.5]);
+    }
+
+    #[test]
+    fn apply_gamma_skips_alpha_and_is_a_no_op_at_one() {
+        let mut image = Array2::from_elem((2, 2), RgbAlpha::new(0.25_f32, 0.5, 1.0, 0.25));
+
+        image.apply_gamma(1.0);
+        assert_eq!(image[[0, 0]].components(), [0.25, 0.5, 1.0, 0.25]);
+
+        image.apply_gamma(2.0);
+        let [red, green, blue, alpha] = image[[1, 1]].components();
+        assert!((red - 0.5_f32).abs() < 1e-6 && (green - 0.5_f32.sqrt()).abs() < 1e-6);
+        assert_eq!((blue, alpha), (1.0, 0.25));
+    }
+
+    #[test]
+    fn adjust_applies_saturation_then_contrast_then_brightness() {
+        let colour = Rgb::new(0.6_f32, 0.4, 0.2);
+        let mut image = Array2::from_elem((1, 2), colour);
+
+        image.adjust(0.0, 1.0, 1.0);
+        assert_eq!(image[[0, 0]].components(), colour.components());
+
+        image.adjust(0.1, 2.0, 0.0);
+        let expected = 0.5 + (luma(&colour.components()) - 0.5) * 2.0 + 0.1;
+        assert!(image[[0, 1]].components().iter().all(|value| (value - expected).abs() < 1e-6));
+
+        // Clamping happens once at the end, so brightness lifts green back from below zero
+        let mut bright = Array2::from_elem((1, 1), Rgb::new(0.9_f32, 0.1, 0.5));
+        bright.adjust(0.3, 1.5, 1.0);
+        let [red, green, blue] = bright[[0, 0]].components();
+        assert!(red == 1.0 && (green - 0.2).abs() < 1e-6 && (blue - 0.8).abs() < 1e-6);
+    }
+}
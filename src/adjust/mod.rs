@@ -0,0 +1,53 @@
+use num_traits::Float;
+
+use crate::{Components, ImageError};
+
+mod arr2;
+
+/// How colours outside `[0, 1]` are brought back into gamut.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GamutMapping {
+    /// Clamp each colour component independently, which may shift the hue of over-saturated colours.
+    Clip,
+    /// Mix the colour toward grey of the same luminance just far enough to bring every component into range, keeping
+    /// its hue.
+    Desaturate,
+}
+
+/// Trait for tonal and colour adjustments of an image.
+///
+/// Adjustments act on the colour components of each pixel and leave any alpha channel untouched.
+pub trait Adjust<C, T, const N: usize>
+where
+    C: Components<T, N> + Clone,
+    T: Float + Send + Sync,
+{
+    /// Raise each colour component to the power `1 / gamma`, clamping the result to `[0, 1]`.
+    ///
+    /// A gamma of one leaves the image unchanged.
+    fn apply_gamma(&mut self, gamma: T);
+
+    /// Apply the standard brightness, contrast and saturation controls, clamping the result to `[0, 1]`.
+    ///
+    /// The steps are applied in order: saturation mixes each colour component with the pixel's luminance
+    /// (`luma + (c - luma) * saturation`), contrast scales about one half (`0.5 + (c - 0.5) * contrast`), then
+    /// brightness is added. Saturation only affects colours with three or more colour components. A brightness of zero
+    /// with unit contrast and saturation leaves the image unchanged.
+    fn adjust(&mut self, brightness: T, contrast: T, saturation: T);
+
+    /// Remove vignetting and sensor non-uniformity by dividing by a flat-field reference image of the same dimensions.
+    ///
+    /// The reference is normalised by the mean of each colour component, so the overall level is preserved. Components
+    /// where the reference is not positive are left unchanged, and results are clamped into the colour type's range.
+    ///
+    /// Returns an error, leaving the image unchanged, if the reference has different dimensions.
+    fn flat_field_correct(&mut self, flat: &Self) -> Result<(), ImageError>;
+
+    /// Bring colours with components outside `[0, 1]` back into gamut by the given method, leaving in-gamut colours
+    /// unchanged.
+    ///
+    /// When desaturating, a luminance outside `[0, 1]` is first clamped, so overexposed colours become white and
+    /// underexposed colours black. Colour constructors only check component ranges in debug builds, so out-of-range
+    /// colours arise only from unchecked construction in release builds.
+    fn gamut_map(&mut self, method: GamutMapping);
+}
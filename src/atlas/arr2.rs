@@ -0,0 +1,86 @@
+use ndarray::Array2;
+use num_traits::Float;
+use std::{fs, path::Path};
+
+use crate::{Atlas, AtlasError, Components, Image, Tiles};
+
+const SIDECAR_EXTENSION: &str = "atlas";
+
+impl<C, T, const N: usize> Atlas<C, T, N> for Array2<Array2<C>>
+where
+    C: Components<T, N> + Copy,
+    T: Float + Send + Sync,
+{
+    fn save_atlas<P: AsRef<Path>>(&self, path: P) -> Result<(), AtlasError> {
+        let (th, tw) = self.first().map_or((0, 0), Array2::dim);
+        if self.iter().any(|tile| tile.dim() != (th, tw)) {
+            return Err(AtlasError::NonUniformTiles);
+        }
+
+        let path = path.as_ref();
+        let (rows, cols) = self.dim();
+        Array2::from_tiles(self).save(path)?;
+        fs::write(path.with_extension(SIDECAR_EXTENSION), format!("{rows} {cols} {th} {tw}\n"))?;
+        Ok(())
+    }
+
+    fn load_atlas<P: AsRef<Path>>(path: P) -> Result<Self, AtlasError> {
+        let path = path.as_ref();
+        let sidecar = fs::read_to_string(path.with_extension(SIDECAR_EXTENSION))?;
+        let values = sidecar
+            .split_whitespace()
+            .map(str::parse::<usize>)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| AtlasError::InvalidSidecar)?;
+        let &[rows, cols, th, tw] = values.as_slice() else {
+            return Err(AtlasError::InvalidSidecar);
+        };
+        if th == 0 || tw == 0 {
+            return Err(AtlasError::InvalidSidecar);
+        }
+
+        let image = Array2::<C>::load(path)?;
+        if image.dim() != (rows * th, cols * tw) {
+            return Err(AtlasError::SizeMismatch);
+        }
+        Ok(image.tiles([th, tw]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chromatic::{Colour, RgbAlpha};
+    use ndarray::Array2;
+    use std::{env, fs, process};
+
+    use crate::{Atlas, AtlasError};
+
+    #[test]
+    fn atlases_round_trip_through_png_and_sidecar() {
+        let tiles = Array2::from_shape_fn((2, 3), |(row, col)| {
+            Array2::from_elem((4, 5), RgbAlpha::<f32>::from_bytes([row as u8 * 100, col as u8 * 80, 7, 200]))
+        });
+        let path = env::temp_dir().join(format!("photo-atlas-{}.png", process::id()));
+
+        tiles.save_atlas(&path).unwrap();
+        let sidecar = fs::read_to_string(path.with_extension("atlas")).unwrap();
+        let loaded = Array2::<Array2<RgbAlpha<f32>>>::load_atlas(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        fs::remove_file(path.with_extension("atlas")).unwrap();
+
+        assert_eq!(sidecar, "2 3 4 5\n");
+        assert_eq!(loaded.dim(), (2, 3));
+        for (a, b) in loaded.iter().zip(&tiles) {
+            assert_eq!(a.dim(), (4, 5));
+            assert!(a.iter().zip(b).all(|(a, b)| a.to_bytes() == b.to_bytes()));
+        }
+    }
+
+    #[test]
+    fn saving_non_uniform_tiles_fails() {
+        let mut tiles = Array2::from_elem((1, 2), Array2::from_elem((2, 2), RgbAlpha::new(0.0_f32, 0.0, 0.0, 1.0)));
+        tiles[[0, 1]] = Array2::from_elem((2, 3), RgbAlpha::new(0.0, 0.0, 0.0, 1.0));
+        let path = env::temp_dir().join(format!("photo-uneven-atlas-{}.png", process::id()));
+        assert!(matches!(tiles.save_atlas(path), Err(AtlasError::NonUniformTiles)));
+    }
+}
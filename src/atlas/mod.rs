@@ -0,0 +1,26 @@
+use ndarray::Array2;
+use num_traits::Float;
+use std::path::Path;
+
+use crate::{AtlasError, Components};
+
+mod arr2;
+
+/// Trait for storing a grid of tiles as a single PNG atlas.
+///
+/// Tiles are packed edge to edge in row-major order, so the atlas is also viewable as an ordinary image. The grid
+/// dimensions and tile size are stored alongside it in a sidecar file with the `.atlas` extension, holding the
+/// `rows columns tile_height tile_width` as whitespace-separated integers.
+pub trait Atlas<C, T, const N: usize>
+where
+    C: Components<T, N> + Clone,
+    T: Float + Send + Sync,
+{
+    /// Write the tile grid to an atlas PNG and its sidecar file.
+    ///
+    /// Every tile must have the same size.
+    fn save_atlas<P: AsRef<Path>>(&self, path: P) -> Result<(), AtlasError>;
+
+    /// Read a tile grid from an atlas PNG and its sidecar file.
+    fn load_atlas<P: AsRef<Path>>(path: P) -> Result<Array2<Array2<C>>, AtlasError>;
+}
@@ -0,0 +1,43 @@
+use std::{
+    error::Error,
+    fmt::{self, Formatter, Result as FmtResult},
+    io::Error as IoError,
+};
+
+use crate::PngError;
+
+/// Errors that can occur during tile atlas operations.
+#[derive(Debug)]
+pub enum AtlasError {
+    IoError(IoError),
+    PngError(PngError),
+    NonUniformTiles,
+    InvalidSidecar,
+    SizeMismatch,
+}
+
+impl fmt::Display for AtlasError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            AtlasError::IoError(err) => write!(f, "IO error: {err}"),
+            AtlasError::PngError(err) => write!(f, "PNG error: {err}"),
+            AtlasError::NonUniformTiles => write!(f, "Atlas tiles are not all the same size"),
+            AtlasError::InvalidSidecar => write!(f, "Invalid atlas sidecar file"),
+            AtlasError::SizeMismatch => write!(f, "Atlas image size does not match its sidecar"),
+        }
+    }
+}
+
+impl Error for AtlasError {}
+
+impl From<IoError> for AtlasError {
+    fn from(err: IoError) -> Self {
+        AtlasError::IoError(err)
+    }
+}
+
+impl From<PngError> for AtlasError {
+    fn from(err: PngError) -> Self {
+        AtlasError::PngError(err)
+    }
+}
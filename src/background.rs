@@ -0,0 +1,107 @@
+//! Static background estimation and foreground detection over image sequences.
+
+use ndarray::{Array2, Zip};
+use num_traits::Float;
+use std::{array, cmp::Ordering};
+
+use crate::{Components, ImageError};
+
+/// Estimate the static background of a sequence of frames as the per-pixel temporal median.
+///
+/// Each component is the median of that component over all frames, so foreground objects that cover a pixel in
+/// fewer than half of the frames are removed. With an even number of frames the upper median is used.
+pub fn background_model<C, T, const N: usize>(frames: &[Array2<C>]) -> Result<Array2<C>, ImageError>
+where
+    C: Components<T, N> + Copy,
+    T: Float + Send + Sync,
+{
+    let first = frames.first().ok_or(ImageError::EmptySequence)?;
+    if frames.iter().any(|frame| frame.dim() != first.dim()) {
+        return Err(ImageError::DimensionMismatch);
+    }
+
+    let mut samples = vec![T::zero(); frames.len()];
+    Ok(Array2::from_shape_fn(first.dim(), |index| {
+        let components = frames.iter().map(|frame| frame[index].components()).collect::<Vec<_>>();
+        C::from_components(array::from_fn(|n| {
+            for (sample, pixel) in samples.iter_mut().zip(&components) {
+                *sample = pixel[n];
+            }
+            let middle = samples.len() / 2;
+            *samples
+                .select_nth_unstable_by(middle, |a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+                .1
+        }))
+    }))
+}
+
+/// Mark the pixels of a frame which differ from the background by more than `threshold` in any component.
+///
+/// Returns an error if the frame and background have different dimensions.
+pub fn foreground_mask<C, T, const N: usize>(
+    frame: &Array2<C>,
+    background: &Array2<C>,
+    threshold: T,
+) -> Result<Array2<bool>, ImageError>
+where
+    C: Components<T, N> + Copy,
+    T: Float + Send + Sync,
+{
+    if frame.dim() != background.dim() {
+        return Err(ImageError::DimensionMismatch);
+    }
+
+    Ok(Zip::from(frame).and(background).map_collect(|pixel, background| {
+        pixel
+            .components()
+            .iter()
+            .zip(background.components())
+            .any(|(&a, b)| (a - b).abs() > threshold)
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use chromatic::Rgb;
+    use ndarray::Array2;
+
+    use super::*;
+
+    #[test]
+    fn moving_object_is_removed_from_background_and_detected() {
+        let scene = Rgb::new(0.25_f32, 0.5, 0.75);
+        let object = Rgb::new(1.0, 0.0, 0.0);
+        let frames = (0..5)
+            .map(|i| {
+                let mut frame = Array2::from_elem((4, 8), scene);
+                frame[[1, i]] = object;
+                frame[[2, i + 1]] = object;
+                frame
+            })
+            .collect::<Vec<_>>();
+
+        let background = background_model(&frames).unwrap();
+        assert!(background.iter().all(|pixel| pixel.components() == scene.components()));
+
+        let mask = foreground_mask(&frames[2], &background, 0.1).unwrap();
+        assert_eq!(mask.iter().filter(|&&m| m).count(), 2);
+        assert!(mask[[1, 2]] && mask[[2, 3]]);
+    }
+
+    #[test]
+    fn mismatched_frames_are_rejected() {
+        let frames = [
+            Array2::from_elem((2, 2), Rgb::new(0.0_f32, 0.0, 0.0)),
+            Array2::from_elem((2, 3), Rgb::new(0.0, 0.0, 0.0)),
+        ];
+        assert!(matches!(background_model(&frames), Err(ImageError::DimensionMismatch)));
+        assert!(matches!(
+            background_model::<Rgb<f32>, f32, 3>(&[]),
+            Err(ImageError::EmptySequence)
+        ));
+        assert!(matches!(
+            foreground_mask(&frames[0], &frames[1], 0.1),
+            Err(ImageError::DimensionMismatch)
+        ));
+    }
+}
@@ -0,0 +1,53 @@
+use chromatic::Colour;
+use num_traits::Float;
+
+use crate::{colour_map_ext::clamp_position, components::cast};
+
+/// A colour map precomputed into a table of evenly spaced colours, so sampling is a constant-time lookup.
+///
+/// Baked maps are built with `ColourMapExt::bake`. Sample positions outside `[0, 1]` are clamped.
+#[derive(Debug, Clone)]
+pub struct BakedColourMap<C, T, const N: usize>
+where
+    C: Colour<T, N>,
+    T: Float + Send + Sync,
+{
+    /// Colours sampled at evenly spaced positions from zero to one inclusive.
+    colours: Vec<C>,
+    /// Table index of position one.
+    scale: T,
+}
+
+impl<C, T, const N: usize> BakedColourMap<C, T, N>
+where
+    C: Colour<T, N> + Clone,
+    T: Float + Send + Sync,
+{
+    /// Create a baked map from colours sampled at evenly spaced positions from zero to one inclusive.
+    ///
+    /// At least two colours are required.
+    pub(crate) fn new(colours: Vec<C>) -> Self {
+        assert!(colours.len() >= 2, "Baked colour maps need at least two entries");
+        let scale = cast(colours.len() - 1);
+        Self { colours, scale }
+    }
+
+    /// Get the table of colours.
+    pub fn colours(&self) -> &[C] {
+        &self.colours
+    }
+
+    /// Sample the table entry nearest to a position.
+    pub fn sample(&self, position: T) -> C {
+        let index = (clamp_position(position) * self.scale).round().to_usize().unwrap_or(0);
+        self.colours[index.min(self.colours.len() - 1)].clone()
+    }
+
+    /// Sample a position by interpolating linearly between the two surrounding table entries.
+    pub fn sample_linear(&self, position: T) -> C {
+        let x = clamp_position(position) * self.scale;
+        let lo = x.floor().to_usize().unwrap_or(0).min(self.colours.len() - 2);
+        let t = (x - cast(lo)).max(T::zero()).min(T::one());
+        C::lerp(&self.colours[lo], &self.colours[lo + 1], t)
+    }
+}
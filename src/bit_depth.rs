@@ -0,0 +1,34 @@
+//! Conversions between 8-bit and 16-bit channel values.
+
+/// Convert a 16-bit channel value to 8 bits, rounding to the nearest value.
+///
+/// Uses `(v * 255 + 32767) / 65535` rather than a bit shift, which would systematically darken the image.
+pub fn downcast_u16_to_u8(value: u16) -> u8 {
+    ((u32::from(value) * 255 + 32767) / 65535) as u8
+}
+
+/// Convert an 8-bit channel value to 16 bits, mapping `255` to `65535`.
+pub fn upcast_u8_to_u16(value: u8) -> u16 {
+    u16::from(value) * 257
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{downcast_u16_to_u8, upcast_u8_to_u16};
+
+    #[test]
+    fn conversions_keep_the_ends_of_the_range() {
+        assert_eq!(downcast_u16_to_u8(65535), 255);
+        assert_eq!(downcast_u16_to_u8(0), 0);
+        assert_eq!(upcast_u8_to_u16(255), 65535);
+        assert_eq!(upcast_u8_to_u16(0), 0);
+    }
+
+    #[test]
+    fn downcast_rounds_rather_than_truncating() {
+        assert_eq!(downcast_u16_to_u8(128), 0);
+        assert_eq!(downcast_u16_to_u8(129), 1);
+        assert_eq!(downcast_u16_to_u8(65535 - 128), 255);
+        assert!((0..=255).all(|value| downcast_u16_to_u8(upcast_u8_to_u16(value)) == value));
+    }
+}
@@ -0,0 +1,109 @@
+use chromatic::Colour;
+use ndarray::Array2;
+use num_traits::Float;
+
+use crate::{Blob, BlobError};
+
+const MAGIC: &[u8; 4] = b"PHTB";
+const HEADER_LEN: usize = 13;
+
+impl<C, T, const N: usize> Blob<C, T, N> for Array2<C>
+where
+    C: Colour<T, N> + Copy,
+    T: Float + Send + Sync,
+{
+    fn to_blob(&self) -> Vec<u8> {
+        let (h, w) = self.dim();
+        let height = u32::try_from(h).expect("Image height must fit in 32 bits");
+        let width = u32::try_from(w).expect("Image width must fit in 32 bits");
+        let channels = u8::try_from(N).expect("Channel count must fit in 8 bits");
+
+        let mut bytes = Vec::with_capacity(HEADER_LEN + h * w * N);
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&height.to_be_bytes());
+        bytes.extend_from_slice(&width.to_be_bytes());
+        bytes.push(channels);
+        for pixel in self {
+            bytes.extend_from_slice(&pixel.to_bytes());
+        }
+        bytes
+    }
+
+    fn from_blob(bytes: &[u8]) -> Result<Self, BlobError> {
+        if bytes.len() < HEADER_LEN || &bytes[0..4] != MAGIC {
+            return Err(BlobError::InvalidHeader);
+        }
+
+        let height = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]) as usize;
+        let width = u32::from_be_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]) as usize;
+        let channels = bytes[12];
+        if channels as usize != N {
+            return Err(BlobError::InvalidChannelCount(channels));
+        }
+
+        let data = &bytes[HEADER_LEN..];
+        let expected = height
+            .checked_mul(width)
+            .and_then(|pixels| pixels.checked_mul(N))
+            .ok_or(BlobError::InvalidHeader)?;
+        if data.len() != expected {
+            return Err(BlobError::InvalidLength {
+                expected,
+                found: data.len(),
+            });
+        }
+
+        let pixels = data
+            .chunks_exact(N)
+            .map(|chunk| C::from_bytes(chunk.try_into().expect("Chunk length matches channel count")))
+            .collect();
+        Ok(Array2::from_shape_vec((height, width), pixels).expect("Pixel count matches blob dimensions"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chromatic::{Colour, Rgb, RgbAlpha};
+    use ndarray::Array2;
+
+    use crate::{Blob, BlobError};
+
+    fn image() -> Array2<Rgb<f32>> {
+        Array2::from_shape_fn((3, 5), |(row, col)| Rgb::from_bytes([(row * 70) as u8, (col * 50) as u8, 9]))
+    }
+
+    #[test]
+    fn blobs_round_trip() {
+        let blob = image().to_blob();
+        assert_eq!(&blob[..13], b"PHTB\0\0\0\x03\0\0\0\x05\x03");
+        assert_eq!(blob.len(), 13 + 3 * 5 * 3);
+
+        let decoded = Array2::<Rgb<f32>>::from_blob(&blob).unwrap();
+        assert_eq!(decoded.dim(), (3, 5));
+        assert!(decoded.iter().zip(&image()).all(|(a, b)| a.to_bytes() == b.to_bytes()));
+    }
+
+    #[test]
+    fn malformed_blobs_are_rejected() {
+        let blob = image().to_blob();
+        assert!(matches!(
+            Array2::<Rgb<f32>>::from_blob(&blob[..blob.len() - 1]),
+            Err(BlobError::InvalidLength { expected: 45, found: 44 })
+        ));
+        assert!(matches!(
+            Array2::<Rgb<f32>>::from_blob(&blob[..8]),
+            Err(BlobError::InvalidHeader)
+        ));
+        assert!(matches!(
+            Array2::<RgbAlpha<f32>>::from_blob(&blob),
+            Err(BlobError::InvalidChannelCount(3))
+        ));
+
+        let mut wrong_magic = blob;
+        wrong_magic[0] = b'X';
+        assert!(matches!(
+            Array2::<Rgb<f32>>::from_blob(&wrong_magic),
+            Err(BlobError::InvalidHeader)
+        ));
+    }
+}
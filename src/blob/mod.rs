@@ -0,0 +1,23 @@
+use chromatic::Colour;
+use ndarray::Array2;
+use num_traits::Float;
+
+use crate::BlobError;
+
+mod arr2;
+
+/// Trait for serialising an image to a compact, dependency-free binary blob.
+///
+/// A blob is the magic bytes `PHTB`, the height and width as big-endian `u32`s, the channel count as a `u8`, and then
+/// the 8-bit components of each pixel in row-major order.
+pub trait Blob<C, T, const N: usize>
+where
+    C: Colour<T, N> + Clone,
+    T: Float + Send + Sync,
+{
+    /// Encode the image as a blob.
+    fn to_blob(&self) -> Vec<u8>;
+
+    /// Decode an image from a blob, validating its header and length.
+    fn from_blob(bytes: &[u8]) -> Result<Array2<C>, BlobError>;
+}
@@ -0,0 +1,26 @@
+use std::{
+    error::Error,
+    fmt::{self, Formatter, Result as FmtResult},
+};
+
+/// Errors that can occur when decoding an image blob.
+#[derive(Debug)]
+pub enum BlobError {
+    InvalidHeader,
+    InvalidChannelCount(u8),
+    InvalidLength { expected: usize, found: usize },
+}
+
+impl fmt::Display for BlobError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            BlobError::InvalidHeader => write!(f, "Invalid image blob header"),
+            BlobError::InvalidChannelCount(channels) => write!(f, "Blob channel count {channels} does not match image"),
+            BlobError::InvalidLength { expected, found } => {
+                write!(f, "Blob pixel data is {found} bytes, expected {expected}")
+            }
+        }
+    }
+}
+
+impl Error for BlobError {}
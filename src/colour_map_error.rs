@@ -0,0 +1,24 @@
+use std::{
+    error::Error,
+    fmt::{self, Formatter, Result as FmtResult},
+};
+
+/// Errors that can occur when building a colour map.
+#[derive(Debug)]
+pub enum ColourMapError {
+    NoStops,
+    PositionOutOfRange(usize),
+    UnsortedPositions(usize),
+}
+
+impl fmt::Display for ColourMapError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            ColourMapError::NoStops => write!(f, "Colour map must have at least one stop"),
+            ColourMapError::PositionOutOfRange(index) => write!(f, "Stop {index} lies outside [0, 1]"),
+            ColourMapError::UnsortedPositions(index) => write!(f, "Stop {index} is not after the previous stop"),
+        }
+    }
+}
+
+impl Error for ColourMapError {}
@@ -0,0 +1,434 @@
+use chromatic::{Colour, ColourMap, Convert, HslAlpha, LabAlpha, RgbAlpha, SrgbAlpha};
+use ndarray::Array2;
+use num_traits::Float;
+use std::{array, cmp::Ordering};
+
+use crate::{
+    BakedColourMap, ColourMapError, ColourMapExt, Components, FromColour, InterpolationSpace, MapInterpolation,
+    components::{cast, distance_squared},
+};
+
+/// Width of the transition between neighbouring bands of a discrete colour map.
+const DISCRETE_TRANSITION: f64 = 1e-6;
+
+impl<C, T, const N: usize> ColourMapExt<C, T, N> for ColourMap<C, T, N>
+where
+    C: Components<T, N> + Clone,
+    T: Float + Send + Sync,
+{
+    fn sample_with(&self, position: T, interpolation: MapInterpolation) -> C {
+        let position = clamp_position(position);
+        let (positions, colours) = (self.positions(), self.colours());
+        if colours.len() == 1 {
+            return colours[0].clone();
+        }
+
+        let (lo, t) = segment(positions, position);
+        match interpolation {
+            MapInterpolation::Nearest => colours[if t > cast(0.5) { lo + 1 } else { lo }].clone(),
+            MapInterpolation::Linear => self.sample(position),
+            MapInterpolation::Cubic => {
+                let span = positions[lo + 1] - positions[lo];
+                let (start, end) = (colours[lo].components(), colours[lo + 1].components());
+                let (m0, m1) = (tangent(positions, colours, lo), tangent(positions, colours, lo + 1));
+
+                // Cubic Hermite basis functions
+                let (t2, t3) = (t * t, t * t * t);
+                let two: T = cast(2);
+                let three: T = cast(3);
+                let h00 = two * t3 - three * t2 + T::one();
+                let h10 = t3 - two * t2 + t;
+                let h01 = three * t2 - two * t3;
+                let h11 = t3 - t2;
+
+                let mut components = [T::zero(); N];
+                for n in 0..N {
+                    components[n] = h00 * start[n] + h10 * span * m0[n] + h01 * end[n] + h11 * span * m1[n];
+                }
+                C::from_components(components)
+            }
+        }
+    }
+
+    fn blend(&self, other: &Self, t: T) -> Self {
+        let t = clamp_position(t);
+        let mut positions = self.positions().iter().chain(other.positions()).copied().collect::<Vec<_>>();
+        positions.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        positions.dedup();
+
+        let colours = positions
+            .iter()
+            .map(|&position| C::lerp(&self.sample(position), &other.sample(position), t))
+            .collect::<Vec<_>>();
+        ColourMap::new(&colours, &positions)
+    }
+
+    fn sample_discrete(&self, position: T, steps: usize) -> C {
+        assert!(steps > 0, "Step count must be non-zero");
+        let count: T = cast(steps);
+        let band = (clamp_position(position) * count).floor().min(count - T::one());
+        self.sample((band + cast(0.5)) / count)
+    }
+
+    fn to_discrete(&self, steps: usize) -> Self {
+        assert!(steps > 0 && steps < 1_000_000, "Step count must be between one and a million");
+        let count: T = cast(steps);
+        let gap: T = cast(DISCRETE_TRANSITION);
+
+        // Hold each band's colour from its start until just before the next band starts
+        let (colours, positions): (Vec<_>, Vec<_>) = (0..steps)
+            .flat_map(|band| {
+                let colour = self.sample((cast::<T, _>(band) + cast(0.5)) / count);
+                let start = cast::<T, _>(band) / count;
+                let end = if band + 1 == steps {
+                    T::one()
+                } else {
+                    cast::<T, _>(band + 1) / count - gap
+                };
+                [(colour.clone(), start), (colour, end)]
+            })
+            .unzip();
+        ColourMap::new(&colours, &positions)
+    }
+
+    fn nearest_stop(&self, colour: &C) -> (usize, T) {
+        let (positions, colours) = (self.positions(), self.colours());
+        if colours.len() == 1 {
+            return (0, positions[0]);
+        }
+
+        // Project onto each segment, keeping the closest point
+        let target = colour.components();
+        let (lo, t, _) = colours
+            .windows(2)
+            .enumerate()
+            .fold((0, T::zero(), T::infinity()), |best, (lo, pair)| {
+                let (a, b) = (pair[0].components(), pair[1].components());
+                let length = distance_squared(&a, &b);
+                let t = if length > T::zero() {
+                    let dot = (0..N).fold(T::zero(), |acc, n| acc + (target[n] - a[n]) * (b[n] - a[n]));
+                    (dot / length).max(T::zero()).min(T::one())
+                } else {
+                    T::zero()
+                };
+                let point: [T; N] = array::from_fn(|n| a[n] + (b[n] - a[n]) * t);
+                let distance = distance_squared(&target, &point);
+                if distance < best.2 { (lo, t, distance) } else { best }
+            });
+
+        let stop = if t > cast(0.5) { lo + 1 } else { lo };
+        (stop, positions[lo] + (positions[lo + 1] - positions[lo]) * t)
+    }
+
+    fn bake(&self, resolution: usize) -> BakedColourMap<C, T, N> {
+        assert!(resolution >= 2, "Resolution must be at least two");
+        let last = cast::<T, _>(resolution - 1);
+        BakedColourMap::new((0..resolution).map(|i| self.sample(cast::<T, _>(i) / last)).collect())
+    }
+
+    fn sample_in(&self, position: T, space: InterpolationSpace) -> C
+    where
+        C: Convert<T> + FromColour<T>,
+    {
+        let colours = self.colours();
+        if colours.len() == 1 {
+            return colours[0].clone();
+        }
+
+        let (lo, t) = segment(self.positions(), clamp_position(position));
+        let (start, end) = (&colours[lo], &colours[lo + 1]);
+        match space {
+            InterpolationSpace::LinearRgb => lerp_in::<RgbAlpha<T>, _, _, 4>(start, end, t),
+            InterpolationSpace::Srgb => lerp_in::<SrgbAlpha<T>, _, _, 4>(start, end, t),
+            InterpolationSpace::Lab => lerp_in::<LabAlpha<T>, _, _, 4>(start, end, t),
+            InterpolationSpace::Hsl => lerp_in::<HslAlpha<T>, _, _, 4>(start, end, t),
+        }
+    }
+
+    fn from_image_row(image: &Array2<C>, row: usize, collapse: bool) -> Self {
+        assert!(row < image.nrows(), "Row must be within the image");
+        assert!(image.ncols() > 0, "Image must have at least one column");
+        let pixels = image.row(row);
+        let last = pixels.len() - 1;
+        if last == 0 {
+            return ColourMap::new(&[pixels[0].clone()], &[T::zero()]);
+        }
+
+        let same = |a: usize, b: usize| pixels[a].components() == pixels[b].components();
+        let (colours, positions): (Vec<_>, Vec<_>) = (0..=last)
+            .filter(|&i| !collapse || i == 0 || i == last || !same(i - 1, i) || !same(i, i + 1))
+            .map(|i| (pixels[i].clone(), cast::<T, _>(i) / cast(last)))
+            .unzip();
+        ColourMap::new(&colours, &positions)
+    }
+
+    fn from_stops(stops: &[(T, C)]) -> Result<Self, ColourMapError> {
+        if stops.is_empty() {
+            return Err(ColourMapError::NoStops);
+        }
+        for (index, &(position, _)) in stops.iter().enumerate() {
+            if !(T::zero()..=T::one()).contains(&position) {
+                return Err(ColourMapError::PositionOutOfRange(index));
+            }
+            if index > 0 && position <= stops[index - 1].0 {
+                return Err(ColourMapError::UnsortedPositions(index));
+            }
+        }
+
+        let (positions, colours): (Vec<_>, Vec<_>) = stops.iter().cloned().unzip();
+        Ok(ColourMap::new(&colours, &positions))
+    }
+}
+
+/// Helper function to clamp a sample position into `[0, 1]`, mapping NaN to zero.
+pub(crate) fn clamp_position<T: Float>(position: T) -> T {
+    position.max(T::zero()).min(T::one())
+}
+
+/// Helper function to find the stop starting the segment containing a position, and the fraction of the way along it.
+///
+/// Positions before the first stop or after the last are placed at the start or end of the outermost segment.
+fn segment<T: Float>(positions: &[T], position: T) -> (usize, T) {
+    let lo = positions
+        .partition_point(|&stop| stop <= position)
+        .saturating_sub(1)
+        .min(positions.len() - 2);
+    let t = (position - positions[lo]) / (positions[lo + 1] - positions[lo]);
+    (lo, t.max(T::zero()).min(T::one()))
+}
+
+/// Helper function to interpolate between two colours after converting them into the colour type `S`.
+fn lerp_in<S, C, T, const M: usize>(start: &C, end: &C, t: T) -> C
+where
+    S: Colour<T, M> + Convert<T> + FromColour<T>,
+    C: Convert<T> + FromColour<T>,
+    T: Float + Send + Sync,
+{
+    C::from_colour(&S::lerp(&S::from_colour(start), &S::from_colour(end), t))
+}
+
+/// Helper function to compute the rate of change of each component at a stop, from its neighbouring stops.
+fn tangent<C, T, const N: usize>(positions: &[T], colours: &[C], index: usize) -> [T; N]
+where
+    C: Components<T, N>,
+    T: Float + Send + Sync,
+{
+    let before = index.saturating_sub(1);
+    let after = (index + 1).min(colours.len() - 1);
+    let (a, b) = (colours[before].components(), colours[after].components());
+    let span = positions[after] - positions[before];
+
+    let mut slope = [T::zero(); N];
+    for n in 0..N {
+        slope[n] = (b[n] - a[n]) / span;
+    }
+    slope
+}
+
+#[cfg(test)]
+mod tests {
+    use chromatic::{ColourMap, Convert, Grey, Rgb};
+    use ndarray::Array2;
+
+    use crate::{ColourMapError, ColourMapExt, Components, InterpolationSpace, MapInterpolation};
+
+    fn map() -> ColourMap<Grey<f64>, f64, 1> {
+        ColourMap::new(&[Grey::new(0.0), Grey::new(0.8), Grey::new(0.2)], &[0.0, 0.25, 1.0])
+    }
+
+    fn sample(position: f64, interpolation: MapInterpolation) -> f64 {
+        map().sample_with(position, interpolation).components()[0]
+    }
+
+    #[test]
+    fn cubic_passes_through_stops() {
+        for (position, grey) in [(0.0, 0.0), (0.25, 0.8), (1.0, 0.2)] {
+            assert!((sample(position, MapInterpolation::Cubic) - grey).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn cubic_is_smooth_across_stops() {
+        let h = 1e-4;
+        let slope = |interpolation, from: f64, to: f64| (sample(to, interpolation) - sample(from, interpolation)) / (to - from);
+
+        let linear_jump = slope(MapInterpolation::Linear, 0.25, 0.25 + h) - slope(MapInterpolation::Linear, 0.25 - h, 0.25);
+        let cubic_jump = slope(MapInterpolation::Cubic, 0.25, 0.25 + h) - slope(MapInterpolation::Cubic, 0.25 - h, 0.25);
+        assert!(linear_jump.abs() > 1.0);
+        assert!(cubic_jump.abs() < 1e-2);
+    }
+
+    #[test]
+    fn nearest_steps_between_stops() {
+        assert_eq!(sample(0.1, MapInterpolation::Nearest), 0.0);
+        assert_eq!(sample(0.2, MapInterpolation::Nearest), 0.8);
+        assert_eq!(sample(0.7, MapInterpolation::Nearest), 0.2);
+    }
+
+    #[test]
+    fn positions_outside_the_map_are_clamped() {
+        assert_eq!(sample(-1.0, MapInterpolation::Linear), 0.0);
+        assert_eq!(sample(2.0, MapInterpolation::Cubic), 0.2);
+    }
+
+    #[test]
+    fn blend_interpolates_between_maps() {
+        let a = ColourMap::new_uniform(&[Rgb::new(0.0, 0.0, 0.0), Rgb::new(1.0, 0.0, 0.0)]);
+        let b = ColourMap::new(
+            &[Rgb::new(0.0, 1.0, 0.0), Rgb::new(0.0, 0.0, 1.0), Rgb::new(0.0, 0.5, 0.5)],
+            &[0.0, 0.3, 1.0],
+        );
+        let close = |x: [f64; 3], y: [f64; 3]| x.iter().zip(y).all(|(x, y)| (x - y).abs() < 1e-12);
+
+        for i in 0..=20 {
+            let position = f64::from(i) / 20.0;
+            let (sa, sb) = (a.sample(position).components(), b.sample(position).components());
+            assert!(close(a.blend(&b, 0.0).sample(position).components(), sa));
+            assert!(close(a.blend(&b, 1.0).sample(position).components(), sb));
+            let mid = a.blend(&b, 0.5).sample(position).components();
+            assert!(close(mid, [0, 1, 2].map(|n| (sa[n] + sb[n]) / 2.0)));
+        }
+    }
+
+    #[test]
+    fn baked_samples_match_within_quantisation_error() {
+        let map = map();
+        let baked = map.bake(101);
+        assert_eq!(baked.colours().len(), 101);
+
+        // The steepest segment changes by 3.2 per unit position, and entries are 0.01 apart
+        for i in 0..=1000 {
+            let position = f64::from(i) / 1000.0;
+            let exact = map.sample(position).components()[0];
+            assert!((baked.sample(position).components()[0] - exact).abs() <= 3.2 * 0.005 + 1e-12);
+            assert!((baked.sample_linear(position).components()[0] - exact).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn sampling_in_a_space_interpolates_there() {
+        let (red, green) = (Rgb::new(1.0, 0.0, 0.0), Rgb::new(0.0, 1.0, 0.0));
+        let map = ColourMap::new_uniform(&[red, green]);
+        let close = |x: f64, y: f64| (x - y).abs() < 1e-6;
+
+        for space in [
+            InterpolationSpace::LinearRgb,
+            InterpolationSpace::Srgb,
+            InterpolationSpace::Lab,
+            InterpolationSpace::Hsl,
+        ] {
+            let [r, g, b] = map.sample_in(0.0, space).components();
+            assert!(close(r, 1.0) && close(g, 0.0) && close(b, 0.0), "{space:?} start");
+        }
+
+        let linear = map.sample_in(0.5, InterpolationSpace::LinearRgb).components();
+        assert!(close(linear[0], 0.5) && close(linear[1], 0.5));
+
+        let srgb = map.sample_in(0.5, InterpolationSpace::Srgb).to_srgb();
+        assert!(close(srgb.red(), 0.5) && close(srgb.green(), 0.5));
+
+        // The Lab midpoint lies just outside the RGB gamut, so clipping shifts its lightness slightly
+        let lightness = map.sample_in(0.5, InterpolationSpace::Lab).to_lab().lightness();
+        assert!((lightness - (red.to_lab().lightness() + green.to_lab().lightness()) / 2.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn image_row_stops_are_evenly_spaced() {
+        let greys = [0.0, 0.2, 0.2, 0.2, 0.6, 1.0];
+        let image = Array2::from_shape_fn((2, 6), |(row, col)| Grey::new(if row == 1 { greys[col] } else { 0.5 }));
+
+        let full = ColourMap::from_image_row(&image, 1, false);
+        assert_eq!(full.positions(), [0.0, 0.2, 0.4, 0.6, 0.8, 1.0]);
+        assert!(
+            full.colours()
+                .iter()
+                .zip(greys)
+                .all(|(colour, grey)| colour.components() == [grey])
+        );
+
+        let collapsed = ColourMap::from_image_row(&image, 1, true);
+        assert_eq!(collapsed.positions(), [0.0, 0.2, 0.6, 0.8, 1.0]);
+        for i in 0..=50 {
+            let position = f64::from(i) / 50.0;
+            let (a, b) = (
+                full.sample(position).components()[0],
+                collapsed.sample(position).components()[0],
+            );
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Row must be within the image")]
+    fn image_row_must_be_in_bounds() {
+        let image = Array2::from_elem((2, 3), Grey::new(0.5_f64));
+        let _ = ColourMap::from_image_row(&image, 2, false);
+    }
+
+    #[test]
+    fn stops_give_non_uniform_gradients() {
+        let (sea, shore, peak) = (Grey::new(0.0), Grey::new(0.5), Grey::new(1.0));
+        let map = ColourMap::from_stops(&[(0.0, sea), (0.49, sea), (0.51, shore), (1.0, peak)]).unwrap();
+        let grey = |position: f64| map.sample_with(position, MapInterpolation::Linear).components()[0];
+
+        assert_eq!(grey(0.3), 0.0);
+        assert!((grey(0.5) - 0.25).abs() < 1e-12);
+        assert!((grey(0.755) - 0.75).abs() < 1e-12);
+        assert_eq!(grey(1.5), 1.0);
+    }
+
+    #[test]
+    fn stops_are_validated() {
+        let grey = Grey::new(0.5_f64);
+        assert!(matches!(
+            ColourMap::<Grey<f64>, f64, 1>::from_stops(&[]),
+            Err(ColourMapError::NoStops)
+        ));
+        assert!(matches!(
+            ColourMap::from_stops(&[(0.0, grey), (1.5, grey)]),
+            Err(ColourMapError::PositionOutOfRange(1))
+        ));
+        assert!(matches!(
+            ColourMap::from_stops(&[(0.0, grey), (0.6, grey), (0.6, grey)]),
+            Err(ColourMapError::UnsortedPositions(2))
+        ));
+    }
+
+    #[test]
+    fn discrete_sampling_takes_band_centres() {
+        let ramp = ColourMap::new_uniform(&[Grey::new(0.0_f64), Grey::new(1.0)]);
+        let discrete = |position| ramp.sample_discrete(position, 4).components()[0];
+        assert_eq!(discrete(-1.0), 0.125);
+        assert_eq!(discrete(0.2), 0.125);
+        assert_eq!(discrete(0.25), 0.375);
+        assert_eq!(discrete(0.99), 0.875);
+        assert_eq!(discrete(1.0), 0.875);
+
+        let banded = ramp.to_discrete(4);
+        for position in [0.0, 0.1, 0.24, 0.25, 0.6, 0.74, 0.75, 1.0] {
+            assert!((banded.sample(position).components()[0] - discrete(position)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn nearest_stop_inverts_sampling() {
+        let map = ColourMap::new(
+            &[Rgb::new(1.0, 0.0, 0.0), Rgb::new(0.0, 1.0, 0.0), Rgb::new(0.0, 0.0, 1.0)],
+            &[0.0_f64, 0.2, 1.0],
+        );
+
+        for (position, stop) in [(0.0, 0), (0.05, 0), (0.15, 1), (0.2, 1), (0.5, 1), (0.7, 2), (1.0, 2)] {
+            let (index, found) = map.nearest_stop(&map.sample(position));
+            assert_eq!(index, stop, "{position}");
+            assert!((found - position).abs() < 1e-12, "{position}");
+        }
+
+        // Colours off the map take the position of their closest point on it
+        let (index, found) = map.nearest_stop(&Rgb::new(0.6, 0.6, 0.2));
+        assert_eq!(index, 0);
+        assert!((found - 0.1).abs() < 1e-12);
+
+        let single = ColourMap::new(&[Grey::new(0.3)], &[0.4]);
+        assert_eq!(single.nearest_stop(&Grey::new(0.9)), (0, 0.4));
+    }
+}
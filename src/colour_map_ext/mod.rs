@@ -0,0 +1,105 @@
+use chromatic::Convert;
+use ndarray::Array2;
+use num_traits::Float;
+
+use crate::{BakedColourMap, ColourMapError, Components, FromColour};
+
+mod colour_map;
+
+pub(crate) use colour_map::clamp_position;
+
+/// How a colour map blends between its stops.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapInterpolation {
+    /// Take the colour of the nearest stop, giving a stepped palette.
+    Nearest,
+    /// Blend the two surrounding stops linearly, as `ColourMap::sample` does.
+    Linear,
+    /// Blend the surrounding stops with a cubic Hermite spline, whose first derivative is continuous across stops.
+    Cubic,
+}
+
+/// Colour space in which a colour map blends between its stops.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterpolationSpace {
+    /// Linear RGB, which mixes light physically.
+    LinearRgb,
+    /// Gamma-encoded sRGB, which matches how most image editors blend.
+    Srgb,
+    /// CIE Lab, which changes lightness evenly and avoids the dark middle of linear RGB blends between saturated hues.
+    Lab,
+    /// HSL, which travels around the hue circle by the shorter path.
+    Hsl,
+}
+
+/// Extension trait adding sampling options and constructors to chromatic's `ColourMap`.
+///
+/// Sample positions outside `[0, 1]` are clamped, so they take the colour at the nearest end of the map.
+pub trait ColourMapExt<C, T, const N: usize>
+where
+    C: Components<T, N> + Clone,
+    T: Float + Send + Sync,
+{
+    /// Sample the colour map at a position using the given interpolation between stops.
+    ///
+    /// Cubic tangents are the slopes between each stop's neighbours, with one-sided slopes at the ends, so the curve
+    /// passes through every stop. Components are interpolated directly and clamped into range, so any cubic overshoot
+    /// near the edge of the range is clipped.
+    fn sample_with(&self, position: T, interpolation: MapInterpolation) -> C;
+
+    /// Blend with another colour map, giving a map whose colour at each position interpolates the two maps' colours by
+    /// `t`, clamped to `[0, 1]`.
+    ///
+    /// The result has a stop at every position of either map, so it matches the blended samples exactly wherever the
+    /// colour type interpolates linearly.
+    fn blend(&self, other: &Self, t: T) -> Self;
+
+    /// Sample the colour map in `steps` equal bands over `[0, 1]`, each taking the colour at the centre of its band.
+    ///
+    /// There must be at least one step.
+    fn sample_discrete(&self, position: T, steps: usize) -> C;
+
+    /// Build a banded colour map whose samples match `sample_discrete` with the same number of steps.
+    ///
+    /// Each band boundary is a sharp transition over a width of `1e-6`, so there must be at least one step and fewer
+    /// than a million.
+    fn to_discrete(&self, steps: usize) -> Self
+    where
+        Self: Sized;
+
+    /// Find where on the colour map a colour sits, inverting `sample`.
+    ///
+    /// The colour is projected onto each segment between adjacent stops, measuring Euclidean distance over the
+    /// components the map interpolates, so the comparison happens in the colour type's own space. Returns the index of
+    /// the stop nearest the closest point along its segment, and that point's position in `[0, 1]`.
+    fn nearest_stop(&self, colour: &C) -> (usize, T);
+
+    /// Precompute the colour map into a table of `resolution` evenly spaced samples, including both ends, for fast
+    /// repeated sampling.
+    ///
+    /// The resolution must be at least two.
+    fn bake(&self, resolution: usize) -> BakedColourMap<C, T, N>;
+
+    /// Sample the colour map at a position, blending the two surrounding stops in the given colour space.
+    ///
+    /// The stops are converted into the space, with alpha, interpolated linearly there, and converted back.
+    fn sample_in(&self, position: T, space: InterpolationSpace) -> C
+    where
+        C: Convert<T> + FromColour<T>;
+
+    /// Build a colour map whose stops are the pixels of one image row, evenly spaced over `[0, 1]`.
+    ///
+    /// With `collapse`, the interior pixels of each run of identical pixels are dropped, leaving a stop at each end of
+    /// the run, which gives fewer stops without changing the sampled colours. The row must be in bounds and the image
+    /// must have at least one column.
+    fn from_image_row(image: &Array2<C>, row: usize, collapse: bool) -> Self;
+
+    /// Build a colour map from explicit `(position, colour)` stops, interpolating between adjacent stops.
+    ///
+    /// Positions must lie in `[0, 1]` and strictly increase, and sampling before the first stop or after the last takes
+    /// that stop's colour. A sharp transition is made with two stops a small distance apart. Returns an error if there
+    /// are no stops or a position is out of range or out of order.
+    fn from_stops(stops: &[(T, C)]) -> Result<Self, ColourMapError>
+    where
+        Self: Sized;
+}
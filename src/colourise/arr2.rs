@@ -0,0 +1,159 @@
+use chromatic::ColourMap;
+use ndarray::Array2;
+use num_traits::Float;
+use std::cmp::Ordering;
+
+use crate::{ColourMapExt, Colourise, Components, components::cast};
+
+impl<T> Colourise<T> for Array2<T>
+where
+    T: Float + Send + Sync,
+{
+    fn colourise<C, F>(&self, sampler: F) -> Array2<C>
+    where
+        F: Fn(T) -> C,
+    {
+        self.map(|&value| sampler(value))
+    }
+
+    fn colourise_smoothed<C, F>(&self, upscale: usize, sampler: F) -> Array2<C>
+    where
+        F: Fn(T) -> C,
+    {
+        assert!(upscale > 0, "Upscale factor must be non-zero");
+        let (h, w) = self.dim();
+
+        // Map output pixel centres back onto source pixel centres
+        let scale: T = cast(upscale);
+        let half: T = cast(0.5);
+        let source = |index: usize, len: usize| {
+            let x = ((cast::<T, _>(index) + half) / scale - half)
+                .max(T::zero())
+                .min(cast(len - 1));
+            let lo = x.floor().to_usize().expect("Coordinate is non-negative");
+            (lo, (lo + 1).min(len - 1), x - x.floor())
+        };
+
+        Array2::from_shape_fn((h * upscale, w * upscale), |(row, col)| {
+            let (r0, r1, fr) = source(row, h);
+            let (c0, c1, fc) = source(col, w);
+            let top = self[[r0, c0]] + (self[[r0, c1]] - self[[r0, c0]]) * fc;
+            let bottom = self[[r1, c0]] + (self[[r1, c1]] - self[[r1, c0]]) * fc;
+            sampler(top + (bottom - top) * fr)
+        })
+    }
+
+    fn colourise_equalised<C, F>(&self, sampler: F) -> Array2<C>
+    where
+        F: Fn(T) -> C,
+    {
+        let mut sorted = self.iter().copied().collect::<Vec<_>>();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+        // Cumulative count of values at or below each value, rescaled so the minimum maps to zero
+        let cdf = |value: T| sorted.partition_point(|&x| x <= value);
+        let min_count = sorted.first().map_or(0, |&min| cdf(min));
+        let range = sorted.len() - min_count;
+        self.map(|&value| {
+            if range == 0 {
+                return sampler(T::zero());
+            }
+            sampler(cast::<T, _>(cdf(value) - min_count) / cast(range))
+        })
+    }
+
+    fn colourise_stepped<C, F>(&self, steps: usize, sampler: F) -> Array2<C>
+    where
+        F: Fn(T) -> C,
+    {
+        assert!(steps > 0, "Step count must be non-zero");
+
+        let count: T = cast(steps);
+        let half: T = cast(0.5);
+        self.map(|&value| {
+            let x = value.max(T::zero()).min(T::one());
+            let band = (x * count).floor().min(count - T::one());
+            sampler((band + half) / count)
+        })
+    }
+
+    fn decolourise<C, F, const N: usize>(image: &Array2<C>, resolution: usize, sampler: F) -> Self
+    where
+        C: Components<T, N> + Clone,
+        F: Fn(T) -> C,
+    {
+        assert!(resolution > 0, "Resolution must be non-zero");
+
+        let count: T = cast(resolution);
+        let samples = (0..=resolution).map(|i| sampler(cast::<T, _>(i) / count)).collect::<Vec<_>>();
+        let map = ColourMap::new_uniform(&samples);
+        image.map(|pixel| map.nearest_stop(pixel).1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chromatic::{ColourMap, Grey, Rgb};
+    use ndarray::{Array2, array};
+
+    use crate::{Colourise, Components};
+
+    #[test]
+    fn colourise_smoothed_upscales_and_interpolates() {
+        let map = ColourMap::new_uniform(&[Grey::new(0.0_f32), Grey::new(1.0)]);
+        let field = array![[0.0_f32, 1.0], [0.0, 1.0]];
+
+        let image = field.colourise_smoothed(4, |x| map.sample(x));
+        assert_eq!(image.dim(), (8, 8));
+
+        let row = image.row(3).iter().map(|pixel| pixel.components()[0]).collect::<Vec<_>>();
+        assert!(row[0].abs() < 1e-6 && (row[7] - 1.0).abs() < 1e-6);
+        for pair in row.windows(2) {
+            assert!(pair[1] >= pair[0] && pair[1] - pair[0] <= 0.25 + 1e-6);
+        }
+    }
+
+    #[test]
+    fn colourise_equalised_spreads_skewed_fields_over_the_whole_map() {
+        let map = ColourMap::new_uniform(&[Grey::new(0.0_f32), Grey::new(1.0)]);
+        let field = Array2::from_shape_fn((8, 8), |(row, col)| ((row * 8 + col) as f32 / 63.0).powi(8));
+
+        let plain = field.colourise(|x| map.sample(x));
+        let below_half = plain.iter().filter(|pixel| pixel.components()[0] < 0.5).count();
+        assert!(below_half > 56);
+
+        let equalised = field.colourise_equalised(|x| map.sample(x));
+        let values = equalised.iter().map(|pixel| pixel.components()[0]).collect::<Vec<_>>();
+        assert!(values[0].abs() < 1e-6 && (values[63] - 1.0).abs() < 1e-6);
+        let below_half = values.iter().filter(|&&value| value < 0.5).count();
+        assert!((31..=33).contains(&below_half));
+    }
+
+    #[test]
+    fn colourise_stepped_samples_band_centres() {
+        let map = ColourMap::new_uniform(&[Grey::new(0.0_f32), Grey::new(1.0)]);
+        let field = array![[-0.5_f32, 0.0, 0.24, 0.26, 0.74, 1.0, 2.0]];
+
+        let banded = field.colourise_stepped(2, |x| map.sample(x));
+        let values = banded.iter().map(|pixel| pixel.components()[0]).collect::<Vec<_>>();
+        assert_eq!(values, [0.25, 0.25, 0.25, 0.25, 0.75, 0.75, 0.75]);
+
+        let single = field.colourise_stepped(1, |x| map.sample(x));
+        assert!(single.iter().all(|pixel| pixel.components()[0] == 0.5));
+    }
+
+    #[test]
+    fn decolourise_inverts_colourise() {
+        let map = ColourMap::new_uniform(&[Rgb::new(1.0_f32, 0.0, 0.0), Rgb::new(0.0, 1.0, 0.0), Rgb::new(0.0, 0.0, 1.0)]);
+        let field = array![[0.0_f32, 0.1, 0.35], [0.5, 0.8, 1.0]];
+
+        let image = field.colourise(|x| map.sample(x));
+        let recovered = Array2::decolourise(&image, 2, |x| map.sample(x));
+        assert!(recovered.iter().zip(&field).all(|(a, b)| (a - b).abs() < 1e-5));
+
+        // Colours off the path take the value of their closest point on it
+        let off_path = array![[Rgb::new(0.6_f32, 0.6, 0.2)]];
+        let value = Array2::decolourise(&off_path, 2, |x| map.sample(x))[[0, 0]];
+        assert!((value - 0.25).abs() < 1e-5, "{value}");
+    }
+}
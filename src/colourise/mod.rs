@@ -0,0 +1,50 @@
+use ndarray::Array2;
+use num_traits::Float;
+
+use crate::Components;
+
+mod arr2;
+
+/// Trait for turning a scalar field into an image by sampling a colour for each value.
+///
+/// The sampler is typically a colour map lookup, such as `|x| map.sample(x)`.
+pub trait Colourise<T>
+where
+    T: Float + Send + Sync,
+{
+    /// Map each value of the field to a colour.
+    fn colourise<C, F>(&self, sampler: F) -> Array2<C>
+    where
+        F: Fn(T) -> C;
+
+    /// Bilinearly upsample the field by an integer factor, then map each value to a colour.
+    fn colourise_smoothed<C, F>(&self, upscale: usize, sampler: F) -> Array2<C>
+    where
+        F: Fn(T) -> C;
+
+    /// Histogram-equalise the field, then map each value to a colour.
+    ///
+    /// Values are replaced by their normalised rank, so the smallest value samples `0` and the largest samples `1`
+    /// regardless of how the data is distributed.
+    fn colourise_equalised<C, F>(&self, sampler: F) -> Array2<C>
+    where
+        F: Fn(T) -> C;
+
+    /// Map each value to a colour in discrete bands.
+    ///
+    /// Values are clamped to `[0, 1]`, which is divided into `steps` equal intervals, and each value samples the centre
+    /// of its interval. There must be at least one step.
+    fn colourise_stepped<C, F>(&self, steps: usize, sampler: F) -> Array2<C>
+    where
+        F: Fn(T) -> C;
+
+    /// Recover the scalar field behind a colourised image, inverting `colourise` for the same sampler.
+    ///
+    /// The sampler is evaluated at `resolution + 1` evenly spaced values across `[0, 1]`, which become the stops of a
+    /// uniform colour map, and each pixel takes the position found by `ColourMapExt::nearest_stop` on that map. Values
+    /// are exact for maps that interpolate linearly between stops at the sampled positions.
+    fn decolourise<C, F, const N: usize>(image: &Array2<C>, resolution: usize, sampler: F) -> Array2<T>
+    where
+        C: Components<T, N> + Clone,
+        F: Fn(T) -> C;
+}
@@ -0,0 +1,122 @@
+use ndarray::Array2;
+use num_traits::Float;
+
+use crate::{
+    Compare, Components,
+    components::{cast, luma},
+};
+
+impl<C, T, const N: usize> Compare<C, T, N> for Array2<C>
+where
+    C: Components<T, N> + Copy,
+    T: Float + Send + Sync,
+{
+    fn mse(&self, other: &Self) -> [T; N] {
+        assert_eq!(self.dim(), other.dim(), "Images must have matching dimensions");
+
+        let mut sum = [T::zero(); N];
+        for (a, b) in self.iter().zip(other) {
+            for (total, (x, y)) in sum.iter_mut().zip(a.components().into_iter().zip(b.components())) {
+                *total = *total + (x - y) * (x - y);
+            }
+        }
+        let count: T = cast(self.len().max(1));
+        sum.map(|total| total / count)
+    }
+
+    fn psnr(&self, other: &Self) -> T {
+        let mse = self.mse(other).iter().fold(T::zero(), |acc, &e| acc + e) / cast(N);
+        if mse == T::zero() {
+            return T::infinity();
+        }
+        cast::<T, _>(-10) * mse.log10()
+    }
+
+    fn approx_eq(&self, other: &Self, tolerance: T) -> bool {
+        self.dim() == other.dim() && self.first_diff(other, tolerance).is_none()
+    }
+
+    fn first_diff(&self, other: &Self, tolerance: T) -> Option<[usize; 2]> {
+        assert_eq!(self.dim(), other.dim(), "Images must have matching dimensions");
+
+        self.indexed_iter()
+            .zip(other)
+            .find(|((_, a), b)| {
+                a.components()
+                    .into_iter()
+                    .zip(b.components())
+                    .any(|(x, y)| (x - y).is_nan() || (x - y).abs() > tolerance)
+            })
+            .map(|(((row, col), _), _)| [row, col])
+    }
+
+    fn difference_hash(&self) -> u64 {
+        let (h, w) = self.dim();
+        if h == 0 || w == 0 {
+            return 0;
+        }
+
+        // Average the luminance over each cell, giving every cell at least one pixel
+        let span = |i: usize, cells: usize, len: usize| {
+            let start = (i * len / cells).min(len - 1);
+            (start, ((i + 1) * len / cells).clamp(start + 1, len))
+        };
+        let cells = Array2::from_shape_fn((8, 9), |(i, j)| {
+            let (r0, r1) = span(i, 8, h);
+            let (c0, c1) = span(j, 9, w);
+            let total = (r0..r1)
+                .flat_map(|row| (c0..c1).map(move |col| [row, col]))
+                .fold(T::zero(), |acc, index| acc + luma(&self[index].components()));
+            total / cast((r1 - r0) * (c1 - c0))
+        });
+
+        (0..8)
+            .flat_map(|i| (0..8).map(move |j| (i, j)))
+            .enumerate()
+            .fold(0, |hash, (bit, (i, j))| {
+                if cells[[i, j]] < cells[[i, j + 1]] {
+                    hash | 1 << bit
+                } else {
+                    hash
+                }
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chromatic::Rgb;
+    use ndarray::Array2;
+
+    use crate::Compare;
+
+    #[test]
+    fn mse_and_psnr_match_a_known_error() {
+        let a = Array2::from_elem((2, 2), Rgb::new(0.5_f32, 0.5, 0.5));
+        let mut b = a.clone();
+        b[[1, 0]] = Rgb::new(1.0, 0.0, 0.5);
+
+        assert_eq!(a.mse(&b), [0.0625, 0.0625, 0.0]);
+        let expected = 10.0 * (3.0_f32 / 0.125).log10();
+        assert!((a.psnr(&b) - expected).abs() < 1e-4);
+        assert_eq!(a.psnr(&a), f32::INFINITY);
+    }
+
+    #[test]
+    fn approx_eq_reports_the_first_pixel_beyond_tolerance() {
+        let a = Array2::from_elem((3, 4), Rgb::new(0.5_f32, 0.5, 0.5));
+        let mut b = a.clone();
+        b[[1, 3]] = Rgb::new(0.5, 0.52, 0.5);
+        b[[2, 0]] = Rgb::new(0.4, 0.5, 0.5);
+
+        assert!(a.approx_eq(&b, 0.2));
+        assert!(!a.approx_eq(&b, 0.05));
+        assert_eq!(a.first_diff(&b, 0.05), Some([2, 0]));
+        assert_eq!(a.first_diff(&b, 0.01), Some([1, 3]));
+        assert_eq!(a.first_diff(&b, 0.2), None);
+
+        b[[0, 0]] = Rgb::new(f32::NAN, 0.5, 0.5);
+        assert_eq!(a.first_diff(&b, 1.0), Some([0, 0]));
+        assert!(!a.approx_eq(&Array2::from_elem((4, 3), Rgb::new(0.5, 0.5, 0.5)), 1.0));
+    }
+}
@@ -0,0 +1,40 @@
+use num_traits::Float;
+
+use crate::Components;
+
+mod arr2;
+
+/// Trait for measuring the difference between images.
+///
+/// Pixelwise measures require both images to have the same dimensions.
+pub trait Compare<C, T, const N: usize>
+where
+    C: Components<T, N> + Clone,
+    T: Float + Send + Sync,
+{
+    /// Compute the mean squared error of each component.
+    fn mse(&self, other: &Self) -> [T; N];
+
+    /// Compute the peak signal-to-noise ratio in decibels, using a peak value of one.
+    ///
+    /// The error is averaged over all components, and identical images give infinity.
+    fn psnr(&self, other: &Self) -> T;
+
+    /// Check whether two images have the same dimensions and every pair of corresponding components differs by at most
+    /// `tolerance`.
+    fn approx_eq(&self, other: &Self, tolerance: T) -> bool;
+
+    /// Find the `[row, column]` of the first pixel, in row-major order, with a component differing from the other image
+    /// by more than `tolerance`.
+    ///
+    /// NaN components always count as differing. Returns `None` if every pixel is within tolerance. Both images must have
+    /// the same dimensions.
+    fn first_diff(&self, other: &Self, tolerance: T) -> Option<[usize; 2]>;
+
+    /// Compute a 64-bit perceptual difference hash of the image's luminance.
+    ///
+    /// The image is area-averaged down to an `8 x 9` grid, and each bit records whether a cell is darker than its right
+    /// neighbour, in row-major order from the least significant bit. Similar images give hashes with a small Hamming
+    /// distance, whatever their size.
+    fn difference_hash(&self) -> u64;
+}
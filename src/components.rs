@@ -0,0 +1,245 @@
+//! Access to the raw components of a colour, and helpers for working with them.
+//!
+//! Channel layouts follow the PNG colour types: one component is grey, two are grey and alpha, three are colour, and
+//! four are colour and alpha.
+
+use chromatic::{
+    Colour, Convert, Grey, GreyAlpha, Hsl, HslAlpha, Hsv, HsvAlpha, Lab, LabAlpha, Rgb, RgbAlpha, Srgb, SrgbAlpha, Xyz,
+    XyzAlpha,
+};
+use ndarray::{ArrayBase, Data, Ix2};
+use num_traits::{Float, ToPrimitive};
+use std::cmp::Ordering;
+
+/// Component-wise access to a colour, in the colour type's own space, with any alpha component last.
+///
+/// Image operations that do arithmetic on pixels read and rebuild colours through this trait. Components outside the
+/// range the colour type accepts are clamped into it when a colour is rebuilt.
+pub trait Components<T, const N: usize>: Colour<T, N>
+where
+    T: Float + Send + Sync,
+{
+    /// Get the components of the colour.
+    fn components(&self) -> [T; N];
+
+    /// Build a colour from its components, clamping each into its valid range.
+    fn from_components(components: [T; N]) -> Self;
+}
+
+/// Implement `Components` for a chromatic colour type from its getters, in order, and the range of each.
+macro_rules! impl_components {
+    ($type:ident, $n:literal, $($get:ident: $range:ident),+) => {
+        impl<T: Float + Send + Sync> Components<T, $n> for $type<T> {
+            fn components(&self) -> [T; $n] {
+                [$(self.$get()),+]
+            }
+
+            fn from_components(components: [T; $n]) -> Self {
+                let [$($get),+] = components;
+                Self::new($($range($get)),+)
+            }
+        }
+    };
+}
+
+impl_components!(Grey, 1, grey: unit_range);
+impl_components!(GreyAlpha, 2, grey: unit_range, alpha: unit_range);
+impl_components!(Rgb, 3, red: unit_range, green: unit_range, blue: unit_range);
+impl_components!(RgbAlpha, 4, red: unit_range, green: unit_range, blue: unit_range, alpha: unit_range);
+impl_components!(Srgb, 3, red: unit_range, green: unit_range, blue: unit_range);
+impl_components!(SrgbAlpha, 4, red: unit_range, green: unit_range, blue: unit_range, alpha: unit_range);
+impl_components!(Hsl, 3, hue: hue_range, saturation: unit_range, lightness: unit_range);
+impl_components!(HslAlpha, 4, hue: hue_range, saturation: unit_range, lightness: unit_range, alpha: unit_range);
+impl_components!(Hsv, 3, hue: hue_range, saturation: unit_range, value: unit_range);
+impl_components!(HsvAlpha, 4, hue: hue_range, saturation: unit_range, value: unit_range, alpha: unit_range);
+impl_components!(Lab, 3, lightness: lightness_range, a_star: opponent_range, b_star: opponent_range);
+impl_components!(LabAlpha, 4, lightness: lightness_range, a_star: opponent_range, b_star: opponent_range, alpha: unit_range);
+impl_components!(Xyz, 3, x: non_negative, y: non_negative, z: non_negative);
+impl_components!(XyzAlpha, 4, x: non_negative, y: non_negative, z: non_negative, alpha: unit_range);
+
+/// Construction of a colour by conversion from any chromatic colour type.
+pub trait FromColour<T>: Sized
+where
+    T: Float + Send + Sync,
+{
+    /// Convert a colour of any chromatic colour type into this one.
+    fn from_colour<S: Convert<T>>(colour: &S) -> Self;
+}
+
+/// Implement `FromColour` for a chromatic colour type using the `Convert` method producing it.
+macro_rules! impl_from_colour {
+    ($type:ident, $convert:ident) => {
+        impl<T: Float + Send + Sync> FromColour<T> for $type<T> {
+            fn from_colour<S: Convert<T>>(colour: &S) -> Self {
+                colour.$convert()
+            }
+        }
+    };
+}
+
+impl_from_colour!(Grey, to_grey);
+impl_from_colour!(GreyAlpha, to_grey_alpha);
+impl_from_colour!(Rgb, to_rgb);
+impl_from_colour!(RgbAlpha, to_rgb_alpha);
+impl_from_colour!(Srgb, to_srgb);
+impl_from_colour!(SrgbAlpha, to_srgb_alpha);
+impl_from_colour!(Hsl, to_hsl);
+impl_from_colour!(HslAlpha, to_hsl_alpha);
+impl_from_colour!(Hsv, to_hsv);
+impl_from_colour!(HsvAlpha, to_hsv_alpha);
+impl_from_colour!(Lab, to_lab);
+impl_from_colour!(LabAlpha, to_lab_alpha);
+impl_from_colour!(Xyz, to_xyz);
+impl_from_colour!(XyzAlpha, to_xyz_alpha);
+
+/// Clamp a component into `[0, 1]`, mapping NaN to zero.
+fn unit_range<T: Float>(x: T) -> T {
+    x.max(T::zero()).min(T::one())
+}
+
+/// Pass a hue through unchanged, as colour constructors wrap it into `[0, 360)` themselves, mapping NaN to zero.
+fn hue_range<T: Float>(x: T) -> T {
+    if x.is_finite() { x } else { T::zero() }
+}
+
+/// Clamp a Lab lightness into `[0, 100]`.
+fn lightness_range<T: Float>(x: T) -> T {
+    x.max(T::zero()).min(cast(100))
+}
+
+/// Clamp a Lab opponent component into `[-128, 127]`.
+fn opponent_range<T: Float>(x: T) -> T {
+    x.max(cast(-128)).min(cast(127))
+}
+
+/// Clamp an XYZ component to be non-negative.
+fn non_negative<T: Float>(x: T) -> T {
+    x.max(T::zero())
+}
+
+/// Check whether colours with `N` components carry an alpha channel as their last component.
+pub(crate) const fn has_alpha(n: usize) -> bool {
+    n == 2 || n == 4
+}
+
+/// Number of leading components that hold colour rather than alpha.
+pub(crate) const fn colour_count(n: usize) -> usize {
+    if has_alpha(n) { n - 1 } else { n }
+}
+
+/// Relative luminance of a colour, using Rec. 709 weights for colour components.
+///
+/// Grey colours return their grey component directly.
+pub(crate) fn luma<T: Float, const N: usize>(components: &[T; N]) -> T {
+    if colour_count(N) < 3 {
+        return components[0];
+    }
+
+    components[0] * cast(0.2126) + components[1] * cast(0.7152) + components[2] * cast(0.0722)
+}
+
+/// Mean of each component over a collection of colours.
+///
+/// Returns zeros if the collection is empty.
+pub(crate) fn mean<'a, C, T, const N: usize>(colours: impl IntoIterator<Item = &'a C>) -> [T; N]
+where
+    C: Components<T, N> + 'a,
+    T: Float + Send + Sync,
+{
+    let mut sum = [T::zero(); N];
+    let mut count = 0;
+    for colour in colours {
+        for (total, value) in sum.iter_mut().zip(colour.components()) {
+            *total = *total + value;
+        }
+        count += 1;
+    }
+    if count > 0 {
+        sum.iter_mut().for_each(|total| *total = *total / cast(count));
+    }
+    sum
+}
+
+/// Check whether two images have the same dimensions and equal components at every pixel.
+pub(crate) fn same_pixels<A, B, C, T, const N: usize>(a: &ArrayBase<A, Ix2>, b: &ArrayBase<B, Ix2>) -> bool
+where
+    A: Data<Elem = C>,
+    B: Data<Elem = C>,
+    C: Components<T, N>,
+    T: Float + Send + Sync,
+{
+    a.dim() == b.dim() && a.iter().zip(b).all(|(a, b)| a.components() == b.components())
+}
+
+/// Total ordering of two components, as by `f64::total_cmp`, so NaN sorts consistently rather than comparing equal.
+pub(crate) fn total_cmp<T: Float>(a: &T, b: &T) -> Ordering {
+    let value = |x: &T| x.to_f64().unwrap_or(f64::NAN);
+    value(a).total_cmp(&value(b))
+}
+
+/// Squared Euclidean distance between two sets of components.
+pub(crate) fn distance_squared<T: Float, const N: usize>(a: &[T; N], b: &[T; N]) -> T {
+    a.iter().zip(b).fold(T::zero(), |acc, (&a, &b)| acc + (a - b) * (a - b))
+}
+
+/// Index of the palette entry closest to the given components by Euclidean distance.
+pub(crate) fn nearest<T: Float, const N: usize>(palette: &[[T; N]], components: &[T; N]) -> usize {
+    palette
+        .iter()
+        .map(|entry| distance_squared(entry, components))
+        .enumerate()
+        .fold(
+            (0, T::infinity()),
+            |best, (index, dist)| if dist < best.1 { (index, dist) } else { best },
+        )
+        .0
+}
+
+/// Multiply the colour components by alpha.
+///
+/// Colours without an alpha channel are returned unchanged.
+pub(crate) fn premultiply<T: Float, const N: usize>(mut components: [T; N]) -> [T; N] {
+    if has_alpha(N) {
+        let alpha = components[N - 1];
+        components[..N - 1].iter_mut().for_each(|value| *value = *value * alpha);
+    }
+    components
+}
+
+/// Divide premultiplied colour components by alpha, giving zero colour where alpha is zero.
+///
+/// Colours without an alpha channel are returned unchanged.
+pub(crate) fn unpremultiply<T: Float, const N: usize>(mut components: [T; N]) -> [T; N] {
+    if has_alpha(N) {
+        let alpha = components[N - 1];
+        components[..N - 1].iter_mut().for_each(|value| {
+            *value = if alpha > T::zero() { *value / alpha } else { T::zero() };
+        });
+    }
+    components
+}
+
+/// Composite `src` over `dst` using straight (non-premultiplied) alpha.
+///
+/// Colours without an alpha channel are treated as opaque, so `src` is returned unchanged.
+pub(crate) fn over<T: Float, const N: usize>(src: [T; N], dst: [T; N]) -> [T; N] {
+    if !has_alpha(N) {
+        return src;
+    }
+
+    let (sa, da) = (src[N - 1], dst[N - 1]);
+    let alpha = sa + da * (T::one() - sa);
+    let mut out = [T::zero(); N];
+    if alpha > T::zero() {
+        for n in 0..N - 1 {
+            out[n] = (src[n] * sa + dst[n] * da * (T::one() - sa)) / alpha;
+        }
+    }
+    out[N - 1] = alpha;
+    out
+}
+
+/// Convert a primitive number into the component type.
+pub(crate) fn cast<T: Float, X: ToPrimitive>(x: X) -> T {
+    T::from(x).expect("Value is representable in the component type")
+}
@@ -0,0 +1,226 @@
+use ndarray::{Array2, Ix2, SliceInfo, SliceInfoElem, Zip, s};
+use num_traits::Float;
+use std::array;
+
+use crate::{
+    BlendMode, Components, Composite, ImageError,
+    components::{cast, colour_count, has_alpha, over, premultiply, unpremultiply},
+};
+
+impl<C, T, const N: usize> Composite<C, T, N> for Array2<C>
+where
+    C: Components<T, N> + Copy,
+    T: Float + Send + Sync,
+{
+    fn paste(&mut self, src: &Self, at: [isize; 2]) {
+        if let Some((dst_region, src_region)) = overlap(self.dim(), src.dim(), at) {
+            self.slice_mut(dst_region).assign(&src.slice(src_region));
+        }
+    }
+
+    fn paste_blend(&mut self, src: &Self, at: [isize; 2]) {
+        if let Some((dst_region, src_region)) = overlap(self.dim(), src.dim(), at) {
+            self.slice_mut(dst_region).zip_mut_with(&src.slice(src_region), |dst, src| {
+                *dst = C::from_components(over(src.components(), dst.components()));
+            });
+        }
+    }
+
+    fn blend_region<F>(&mut self, start: [isize; 2], size: [usize; 2], f: F)
+    where
+        F: Fn(C) -> C,
+    {
+        if let Some((region, _)) = overlap(self.dim(), (size[0], size[1]), start) {
+            self.slice_mut(region).mapv_inplace(f);
+        }
+    }
+
+    fn blend(&self, other: &Self, mode: BlendMode) -> Self {
+        assert_eq!(self.dim(), other.dim(), "Images must have matching dimensions");
+
+        let two: T = cast(2);
+        let half: T = cast(0.5);
+        Zip::from(self).and(other).map_collect(|base, layer| {
+            let (mut out, layer) = (base.components(), layer.components());
+            for (a, &b) in out.iter_mut().zip(&layer).take(colour_count(N)) {
+                let value = match mode {
+                    BlendMode::Multiply => *a * b,
+                    BlendMode::Screen => T::one() - (T::one() - *a) * (T::one() - b),
+                    BlendMode::Overlay if *a < half => two * *a * b,
+                    BlendMode::Overlay => T::one() - two * (T::one() - *a) * (T::one() - b),
+                    BlendMode::Add => *a + b,
+                    BlendMode::Subtract => *a - b,
+                    BlendMode::Difference => (*a - b).abs(),
+                };
+                *a = value.max(T::zero()).min(T::one());
+            }
+            C::from_components(out)
+        })
+    }
+
+    fn average(images: &[Self]) -> Result<Self, ImageError> {
+        let first = images.first().ok_or(ImageError::EmptySequence)?;
+        if images.iter().any(|image| image.dim() != first.dim()) {
+            return Err(ImageError::DimensionMismatch);
+        }
+
+        let mut sums = Array2::from_elem(first.dim(), [T::zero(); N]);
+        for image in images {
+            Zip::from(&mut sums).and(image).for_each(|sum, pixel| {
+                for (s, v) in sum.iter_mut().zip(premultiply(pixel.components())) {
+                    *s = *s + v;
+                }
+            });
+        }
+
+        let scale = cast::<T, _>(images.len()).recip();
+        Ok(sums.map(|sum| C::from_components(unpremultiply(sum.map(|s| s * scale)))))
+    }
+
+    fn on_checkerboard<D, const M: usize>(&self, cell: usize, light: D, dark: D) -> Array2<D>
+    where
+        D: Components<T, M>,
+    {
+        assert!(cell > 0, "Checkerboard cells must be non-zero");
+        assert_eq!(M, colour_count(N), "Target must have the colour components without alpha");
+
+        let (light, dark) = (light.components(), dark.components());
+        Array2::from_shape_fn(self.dim(), |(row, col)| {
+            let backdrop = if (row / cell + col / cell).is_multiple_of(2) {
+                light
+            } else {
+                dark
+            };
+            let components = self[[row, col]].components();
+            let alpha = if has_alpha(N) { components[N - 1] } else { T::one() };
+            D::from_components(array::from_fn(|n| components[n] * alpha + backdrop[n] * (T::one() - alpha)))
+        })
+    }
+}
+
+/// Slice information for a two-dimensional region.
+type Region = SliceInfo<[SliceInfoElem; 2], Ix2, Ix2>;
+
+/// Helper function to find the overlapping regions of the destination and a source placed at an offset.
+fn overlap(dst: (usize, usize), src: (usize, usize), at: [isize; 2]) -> Option<(Region, Region)> {
+    let clip = |dst_len: usize, src_len: usize, offset: isize| {
+        let start = offset.max(0);
+        let end = (offset + src_len as isize).min(dst_len as isize);
+        (start < end).then(|| {
+            (
+                start as usize..end as usize,
+                (start - offset) as usize..(end - offset) as usize,
+            )
+        })
+    };
+
+    let (dst_rows, src_rows) = clip(dst.0, src.0, at[0])?;
+    let (dst_cols, src_cols) = clip(dst.1, src.1, at[1])?;
+    Some((s![dst_rows, dst_cols], s![src_rows, src_cols]))
+}
+
+#[cfg(test)]
+mod tests {
+    use chromatic::{Grey, GreyAlpha, Rgb, RgbAlpha};
+    use ndarray::{Array2, array};
+
+    use crate::{BlendMode, Components, Composite, ImageError};
+
+    #[test]
+    fn paste_clips_sources_at_negative_and_overflowing_offsets() {
+        let mut canvas = Array2::from_elem((3, 3), Grey::new(0.0_f32));
+        let src = Array2::from_elem((2, 2), Grey::new(1.0));
+
+        canvas.paste(&src, [-1, -1]);
+        canvas.paste(&src, [2, 2]);
+        let values = canvas.map(|pixel| pixel.components()[0]);
+        assert_eq!(values, array![[1.0, 0.0, 0.0], [0.0, 0.0, 0.0], [0.0, 0.0, 1.0]]);
+
+        canvas.paste(&src, [5, -4]);
+        assert_eq!(canvas.iter().filter(|pixel| pixel.components()[0] > 0.5).count(), 2);
+    }
+
+    #[test]
+    fn paste_blend_composites_over_the_destination() {
+        let mut canvas = Array2::from_elem((2, 2), RgbAlpha::new(0.0_f32, 0.0, 1.0, 1.0));
+        let src = Array2::from_elem((1, 1), RgbAlpha::new(1.0, 0.0, 0.0, 0.5));
+
+        canvas.paste_blend(&src, [1, 1]);
+        let [red, green, blue, alpha] = canvas[[1, 1]].components();
+        assert!((red - 0.5_f32).abs() < 1e-6 && green == 0.0 && (blue - 0.5).abs() < 1e-6 && alpha == 1.0);
+        assert_eq!(canvas[[0, 0]].components(), [0.0, 0.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn blend_modes_match_their_formulas_and_keep_base_alpha() {
+        let base = array![[0.0_f32, 1.0, 0.5, 0.25]].mapv(|grey| GreyAlpha::new(grey, 0.8));
+        let layer = Array2::from_elem((1, 4), GreyAlpha::new(0.5, 0.3));
+
+        for (mode, expected) in [
+            (BlendMode::Multiply, [0.0, 0.5, 0.25, 0.125]),
+            (BlendMode::Screen, [0.5, 1.0, 0.75, 0.625]),
+            (BlendMode::Overlay, [0.0, 1.0, 0.5, 0.25]),
+            (BlendMode::Add, [0.5, 1.0, 1.0, 0.75]),
+            (BlendMode::Subtract, [0.0, 0.5, 0.0, 0.0]),
+            (BlendMode::Difference, [0.5, 0.5, 0.0, 0.25]),
+        ] {
+            let blended = base.blend(&layer, mode);
+            let values = blended.iter().map(|pixel| pixel.components()).collect::<Vec<_>>();
+            assert_eq!(values, expected.map(|grey| [grey, 0.8]), "{mode:?}");
+        }
+    }
+
+    #[test]
+    fn blend_region_maps_in_place_and_clips_silently() {
+        let mut image = Array2::from_elem((3, 4), RgbAlpha::new(0.8_f32, 0.6, 0.4, 1.0));
+        let dim = |pixel: RgbAlpha<f32>| {
+            let [red, green, blue, alpha] = pixel.components();
+            RgbAlpha::new(red * 0.5, green * 0.5, blue * 0.5, alpha)
+        };
+
+        image.blend_region([-1, 2], [3, 5], dim);
+        image.blend_region([5, 5], [2, 2], dim);
+        let dimmed = image.map(|pixel| pixel.components()[0] < 0.5);
+        assert_eq!(
+            dimmed,
+            array![[false, false, true, true], [false, false, true, true], [false; 4]]
+        );
+        assert_eq!(image[[1, 3]].components(), [0.4, 0.3, 0.2, 1.0]);
+    }
+
+    #[test]
+    fn average_weights_colour_by_alpha_and_keeps_transparency() {
+        let clear = RgbAlpha::new(0.0_f32, 1.0, 0.0, 0.0);
+        let a = array![[RgbAlpha::new(1.0, 0.0, 0.0, 1.0), clear]];
+        let b = array![[RgbAlpha::new(0.0, 0.0, 1.0, 0.5), clear]];
+        let c = array![[clear, clear]];
+
+        let averaged = Array2::average(&[a, b, c]).unwrap();
+        let [red, green, blue, alpha] = averaged[[0, 0]].components();
+        assert!((red - 2.0 / 3.0).abs() < 1e-6 && green == 0.0 && (blue - 1.0 / 3.0).abs() < 1e-6);
+        assert!((alpha - 0.5).abs() < 1e-6);
+        assert_eq!(averaged[[0, 1]].components(), [0.0; 4]);
+
+        assert!(matches!(
+            Array2::<RgbAlpha<f32>>::average(&[]),
+            Err(ImageError::EmptySequence)
+        ));
+        let mismatched = [Array2::from_elem((1, 1), clear), Array2::from_elem((1, 2), clear)];
+        assert!(matches!(Array2::average(&mismatched), Err(ImageError::DimensionMismatch)));
+    }
+
+    #[test]
+    fn checkerboard_shows_through_transparency_only() {
+        let mut image = Array2::from_elem((4, 4), RgbAlpha::new(0.0_f32, 0.0, 0.0, 0.0));
+        image[[0, 0]] = RgbAlpha::new(0.3, 0.6, 0.9, 1.0);
+        image[[3, 3]] = RgbAlpha::new(1.0, 1.0, 1.0, 0.5);
+        let (light, dark) = (Rgb::new(0.8_f32, 0.8, 0.8), Rgb::new(0.4, 0.4, 0.4));
+
+        let preview = image.on_checkerboard(2, light, dark);
+        assert_eq!(preview[[0, 0]].components(), [0.3, 0.6, 0.9]);
+        assert_eq!(preview[[1, 1]].components(), [0.8, 0.8, 0.8]);
+        assert_eq!(preview[[0, 2]].components(), [0.4, 0.4, 0.4]);
+        assert_eq!(preview[[2, 1]].components(), [0.4, 0.4, 0.4]);
+        assert_eq!(preview[[3, 3]].components(), [0.9, 0.9, 0.9]);
+    }
+}
@@ -0,0 +1,69 @@
+use ndarray::Array2;
+use num_traits::Float;
+
+use crate::{Components, ImageError};
+
+mod arr2;
+
+/// Photoshop-style layer blend modes, where `a` is the base layer and `b` the blend layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlendMode {
+    /// `a * b`
+    Multiply,
+    /// `1 - (1 - a) * (1 - b)`
+    Screen,
+    /// Multiply where `a < 0.5`, screen elsewhere, each at double strength.
+    Overlay,
+    /// `a + b`
+    Add,
+    /// `a - b`
+    Subtract,
+    /// `|a - b|`
+    Difference,
+}
+
+/// Trait for combining images.
+///
+/// Offsets are given as `[row, column]` and may be negative or extend past the destination, in which case the source is
+/// clipped to the destination bounds.
+pub trait Composite<C, T, const N: usize>
+where
+    C: Components<T, N> + Clone,
+    T: Float + Send + Sync,
+{
+    /// Copy another image into this one at the given offset, overwriting existing pixels.
+    fn paste(&mut self, src: &Array2<C>, at: [isize; 2]);
+
+    /// Alpha-composite another image over this one at the given offset.
+    ///
+    /// Colours without an alpha channel are treated as opaque, making this equivalent to `paste`.
+    fn paste_blend(&mut self, src: &Array2<C>, at: [isize; 2]);
+
+    /// Apply a function in place to every pixel of a rectangular region, given as `[row, column]` and `[height, width]`.
+    ///
+    /// Parts of the region outside the image are clipped silently, so an entirely out-of-bounds region does nothing.
+    fn blend_region<F>(&mut self, start: [isize; 2], size: [usize; 2], f: F)
+    where
+        F: Fn(C) -> C;
+
+    /// Blend another image onto this one, treating this image as the base layer.
+    ///
+    /// The blend is applied per colour component and clamped to `[0, 1]`; alpha is taken from the base layer.
+    /// Both images must have the same dimensions.
+    fn blend(&self, other: &Array2<C>, mode: BlendMode) -> Array2<C>;
+
+    /// Compute the alpha-weighted mean of a stack of images with the same dimensions.
+    ///
+    /// Alpha is the plain mean of the input alphas and colour is averaged weighted by alpha, so pixels which are fully
+    /// transparent in every image stay transparent and do not tint the result. Colours without an alpha channel are
+    /// averaged directly.
+    fn average(images: &[Array2<C>]) -> Result<Array2<C>, ImageError>;
+
+    /// Flatten the image over a checkerboard of `cell x cell` squares for previewing transparency, alternating the
+    /// light and dark colours from a light top-left cell.
+    ///
+    /// The target must have the image's colour components without alpha, and opaque pixels keep their colour exactly.
+    fn on_checkerboard<D, const M: usize>(&self, cell: usize, light: D, dark: D) -> Array2<D>
+    where
+        D: Components<T, M>;
+}
@@ -0,0 +1,250 @@
+use ndarray::{Array2, Array3};
+use num_traits::Float;
+
+use crate::{
+    Channel, Components, Convert, ImageError,
+    components::{cast, colour_count, has_alpha, luma},
+};
+
+impl<C, T, const N: usize> Convert<C, T, N> for Array2<C>
+where
+    C: Components<T, N> + Copy,
+    T: Float + Send + Sync,
+{
+    fn luma_to_alpha<D, const M: usize>(&self) -> Array2<D>
+    where
+        D: Components<T, M>,
+    {
+        check_adds_alpha::<N, M>();
+        self.map(|pixel| {
+            let components = pixel.components();
+            with_alpha(components, luma(&components).max(T::zero()).min(T::one()))
+        })
+    }
+
+    fn colour_key<D, const M: usize>(&self, key: C, tolerance: u8) -> Array2<D>
+    where
+        D: Components<T, M>,
+    {
+        check_adds_alpha::<N, M>();
+        let key = key.to_bytes();
+        self.map(|pixel| {
+            let alpha = if key_distance(pixel.to_bytes(), key) <= tolerance {
+                T::zero()
+            } else {
+                T::one()
+            };
+            with_alpha(pixel.components(), alpha)
+        })
+    }
+
+    fn colour_key_soft<D, const M: usize>(&self, key: C, tolerance: u8) -> Array2<D>
+    where
+        D: Components<T, M>,
+    {
+        check_adds_alpha::<N, M>();
+        let key = key.to_bytes();
+        self.map(|pixel| {
+            let distance = key_distance(pixel.to_bytes(), key);
+            let alpha = if tolerance == 0 {
+                if distance == 0 { T::zero() } else { T::one() }
+            } else {
+                (cast::<T, _>(distance) / cast(tolerance)).min(T::one())
+            };
+            with_alpha(pixel.components(), alpha)
+        })
+    }
+
+    fn convert<D, const M: usize>(&self) -> Array2<D>
+    where
+        D: Components<T, M>,
+    {
+        assert!(
+            (1..=4).contains(&N) && (1..=4).contains(&M),
+            "Layouts must have one to four components"
+        );
+        let (source, target) = (colour_count(N), colour_count(M));
+        self.map(|pixel| {
+            let components = pixel.components();
+            let mut out = [T::zero(); M];
+            match (source, target) {
+                (1, 3) => out[..3].fill(components[0]),
+                (3, 1) => out[0] = luma(&components),
+                _ => out[..target].copy_from_slice(&components[..target]),
+            }
+            if has_alpha(M) {
+                out[M - 1] = if has_alpha(N) { components[N - 1] } else { T::one() };
+            }
+            D::from_components(out)
+        })
+    }
+
+    fn layer(&self, channel: Channel) -> Result<Array2<T>, ImageError> {
+        let index = channel.index(N).ok_or(ImageError::MissingChannel(channel))?;
+        Ok(self.map(|pixel| pixel.components()[index]))
+    }
+
+    fn to_grey_alpha<D>(&self) -> Array2<D>
+    where
+        D: Components<T, 2>,
+    {
+        self.map(|pixel| {
+            let components = pixel.components();
+            let alpha = if has_alpha(N) { components[N - 1] } else { T::one() };
+            D::from_components([luma(&components), alpha])
+        })
+    }
+
+    fn to_planar(&self) -> Array3<T> {
+        let (h, w) = self.dim();
+        Array3::from_shape_fn((N, h, w), |(channel, row, col)| self[[row, col]].components()[channel])
+    }
+
+    fn from_planar(planar: &Array3<T>) -> Self {
+        let (channels, h, w) = planar.dim();
+        assert_eq!(channels, N, "Planar data must have one plane per component");
+        Array2::from_shape_fn((h, w), |(row, col)| {
+            C::from_components(std::array::from_fn(|channel| planar[[channel, row, col]]))
+        })
+    }
+}
+
+/// Helper function to check that a layout without alpha can gain one by converting to `M` components.
+fn check_adds_alpha<const N: usize, const M: usize>() {
+    assert!(!has_alpha(N), "Image already has an alpha channel");
+    assert!(M == N + 1, "Target colour must have exactly one more component");
+}
+
+/// Helper function to append an alpha component to a colour without one.
+fn with_alpha<D, T, const N: usize, const M: usize>(components: [T; N], alpha: T) -> D
+where
+    D: Components<T, M>,
+    T: Float + Send + Sync,
+{
+    let mut out = [T::zero(); M];
+    out[..N].copy_from_slice(&components);
+    out[N] = alpha;
+    D::from_components(out)
+}
+
+/// Helper function to find the largest per-component difference between two byte colours.
+fn key_distance<const N: usize>(a: [u8; N], b: [u8; N]) -> u8 {
+    a.iter().zip(&b).map(|(&a, &b)| a.abs_diff(b)).max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use chromatic::{Colour, GreyAlpha, Rgb, RgbAlpha};
+    use ndarray::{Array2, array};
+
+    use crate::{Channel, Components, Convert, ImageError, components::luma};
+
+    #[test]
+    fn luma_to_alpha_makes_white_opaque_and_black_transparent() {
+        let image = array![[Rgb::new(1.0_f32, 1.0, 1.0), Rgb::new(0.0, 0.0, 0.0), Rgb::new(0.2, 0.6, 0.4)]];
+
+        let keyed = image.luma_to_alpha::<RgbAlpha<f32>, 4>();
+        assert_eq!(keyed[[0, 0]].components(), [1.0, 1.0, 1.0, 1.0]);
+        assert_eq!(keyed[[0, 1]].components(), [0.0, 0.0, 0.0, 0.0]);
+        let [red, green, blue, alpha] = keyed[[0, 2]].components();
+        assert_eq!([red, green, blue], [0.2, 0.6, 0.4]);
+        assert!((alpha - (0.2 * 0.2126 + 0.6 * 0.7152 + 0.4 * 0.0722)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn colour_key_cuts_out_the_key_within_tolerance() {
+        let green = Rgb::<f32>::from_bytes([0, 255, 0]);
+        let image = array![[
+            green,
+            Rgb::from_bytes([4, 250, 0]),
+            Rgb::from_bytes([10, 255, 0]),
+            Rgb::from_bytes([200, 40, 90])
+        ]];
+        let alpha = |keyed: Array2<RgbAlpha<f32>>| keyed.iter().map(|pixel| pixel.components()[3]).collect::<Vec<_>>();
+
+        assert_eq!(alpha(image.colour_key(green, 0)), [0.0, 1.0, 1.0, 1.0]);
+        assert_eq!(alpha(image.colour_key(green, 5)), [0.0, 0.0, 1.0, 1.0]);
+        let keyed = image.colour_key::<RgbAlpha<f32>, 4>(green, 5);
+        assert_eq!(keyed[[0, 3]].to_bytes(), [200, 40, 90, 255]);
+
+        let soft = alpha(image.colour_key_soft(green, 20));
+        assert_eq!(soft[0], 0.0);
+        assert!((soft[1] - 0.25).abs() < 1e-6 && (soft[2] - 0.5).abs() < 1e-6);
+        assert_eq!(soft[3], 1.0);
+    }
+
+    #[test]
+    fn to_grey_alpha_keeps_alpha_and_orientation() {
+        let image = Array2::from_shape_fn((2, 3), |(row, col)| {
+            RgbAlpha::new(
+                col as f32 / 2.0,
+                col as f32 / 2.0,
+                col as f32 / 2.0,
+                if row == 0 { 1.0 } else { 0.25 },
+            )
+        });
+
+        let grey = image.to_grey_alpha::<GreyAlpha<f32>>();
+        assert_eq!(grey.dim(), (2, 3));
+        for ((row, col), pixel) in grey.indexed_iter() {
+            let [value, alpha] = pixel.components();
+            assert!((value - col as f32 / 2.0).abs() < 1e-6);
+            assert_eq!(alpha, if row == 0 { 1.0 } else { 0.25 });
+        }
+
+        let opaque = array![[Rgb::new(0.2_f32, 0.6, 0.4)]].to_grey_alpha::<GreyAlpha<f32>>();
+        assert_eq!(opaque[[0, 0]].components()[1], 1.0);
+    }
+
+    #[test]
+    fn named_layers_exist_only_in_matching_layouts() {
+        let colour = array![[RgbAlpha::new(0.1_f32, 0.2, 0.3, 0.4)]];
+        assert_eq!(colour.layer(Channel::Blue).unwrap(), array![[0.3]]);
+        assert_eq!(colour.layer(Channel::Alpha).unwrap(), array![[0.4]]);
+        assert!(matches!(
+            colour.layer(Channel::Grey),
+            Err(ImageError::MissingChannel(Channel::Grey))
+        ));
+
+        let grey = array![[GreyAlpha::new(0.7_f32, 0.9)]];
+        assert_eq!(grey.layer(Channel::Grey).unwrap(), array![[0.7]]);
+        assert!(matches!(
+            grey.layer(Channel::Blue),
+            Err(ImageError::MissingChannel(Channel::Blue))
+        ));
+        assert_eq!(Channel::Alpha.index(2), Some(1));
+        assert_eq!(Channel::Alpha.index(3), None);
+    }
+
+    #[test]
+    fn convert_maps_between_the_common_layouts() {
+        let colour = Rgb::new(0.2_f32, 0.6, 0.4);
+        let rgb = array![[colour]];
+
+        let grey = rgb.convert::<GreyAlpha<f32>, 2>();
+        let [value, alpha] = grey[[0, 0]].components();
+        assert!((value - luma(&colour.components())).abs() < 1e-6 && alpha == 1.0);
+
+        let replicated = array![[GreyAlpha::new(0.3_f32, 0.5)]].convert::<Rgb<f32>, 3>();
+        assert_eq!(replicated[[0, 0]].components(), [0.3, 0.3, 0.3]);
+
+        let opaque = rgb.convert::<RgbAlpha<f32>, 4>();
+        assert_eq!(opaque[[0, 0]].components(), [0.2, 0.6, 0.4, 1.0]);
+
+        let dropped = array![[RgbAlpha::new(0.2_f32, 0.6, 0.4, 0.1)]].convert::<Rgb<f32>, 3>();
+        assert_eq!(dropped[[0, 0]].components(), [0.2, 0.6, 0.4]);
+    }
+
+    #[test]
+    fn planar_round_trip_reproduces_the_image() {
+        let image = Array2::from_shape_fn((3, 5), |(row, col)| Rgb::new(row as f32 / 2.0, col as f32 / 4.0, 0.25));
+
+        let planar = image.to_planar();
+        assert_eq!(planar.dim(), (3, 3, 5));
+        assert_eq!(planar[[1, 2, 4]], 1.0);
+        assert_eq!(planar[[2, 0, 0]], 0.25);
+
+        let restored = Array2::<Rgb<f32>>::from_planar(&planar);
+        assert!(restored.iter().zip(&image).all(|(a, b)| a.components() == b.components()));
+    }
+}
@@ -0,0 +1,97 @@
+use ndarray::{Array2, Array3};
+use num_traits::Float;
+
+use crate::{Components, ImageError};
+
+mod arr2;
+
+/// Named channels of a colour, following the PNG channel layouts.
+///
+/// One component is grey, two are grey and alpha, three are red, green and blue, and four add alpha to those.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    /// First component of a colour image.
+    Red,
+    /// Second component of a colour image.
+    Green,
+    /// Third component of a colour image.
+    Blue,
+    /// First component of a grey image.
+    Grey,
+    /// Last component of a grey or colour image with alpha.
+    Alpha,
+}
+
+impl Channel {
+    /// Component index of the channel within a colour of `n` components, if the layout has it.
+    pub fn index(self, n: usize) -> Option<usize> {
+        match (self, n) {
+            (Channel::Grey, 1 | 2) => Some(0),
+            (Channel::Red, 3 | 4) => Some(0),
+            (Channel::Green, 3 | 4) => Some(1),
+            (Channel::Blue, 3 | 4) => Some(2),
+            (Channel::Alpha, 2 | 4) => Some(n - 1),
+            _ => None,
+        }
+    }
+}
+
+/// Trait for converting an image between colour types with different channel layouts.
+///
+/// Target channel counts are checked at runtime, and mismatched layouts cause a panic.
+pub trait Convert<C, T, const N: usize>
+where
+    C: Components<T, N> + Clone,
+    T: Float + Send + Sync,
+{
+    /// Add an alpha channel equal to each pixel's luminance, clamped to `[0, 1]`, keeping the colour components.
+    ///
+    /// The image must not already have an alpha channel, and the target must have exactly one more component.
+    fn luma_to_alpha<D, const M: usize>(&self) -> Array2<D>
+    where
+        D: Components<T, M>;
+
+    /// Add an alpha channel that makes pixels matching the key colour fully transparent and all others opaque.
+    ///
+    /// Pixels match when every 8-bit component is within `tolerance` of the key, so a tolerance of zero keys only the
+    /// exact colour. The image must not already have an alpha channel, and the target must have exactly one more
+    /// component.
+    fn colour_key<D, const M: usize>(&self, key: C, tolerance: u8) -> Array2<D>
+    where
+        D: Components<T, M>;
+
+    /// Add an alpha channel that ramps from transparent at the key colour to opaque at the edge of the tolerance band.
+    ///
+    /// Alpha is the largest 8-bit component difference from the key divided by `tolerance`, clamped to `[0, 1]`.
+    fn colour_key_soft<D, const M: usize>(&self, key: C, tolerance: u8) -> Array2<D>
+    where
+        D: Components<T, M>;
+
+    /// Desaturate the image to grey with alpha, keeping any existing alpha and treating colours without one as opaque.
+    ///
+    /// Grey is the luminance of the colour components.
+    fn to_grey_alpha<D>(&self) -> Array2<D>
+    where
+        D: Components<T, 2>;
+
+    /// Convert the image to any other grey or colour layout of one to four components.
+    ///
+    /// Colour becomes grey by luminance and grey becomes colour by replication. Alpha is carried over when both layouts
+    /// have it, added as opaque when only the target has it, and dropped otherwise.
+    fn convert<D, const M: usize>(&self) -> Array2<D>
+    where
+        D: Components<T, M>;
+
+    /// Extract one named channel of the image as a scalar field.
+    ///
+    /// Returns an error if the colour type's layout has no such channel.
+    fn layer(&self, channel: Channel) -> Result<Array2<T>, ImageError>;
+
+    /// Copy the image into planar (channel-major) layout, with shape `(N, height, width)`.
+    fn to_planar(&self) -> Array3<T>;
+
+    /// Assemble an image from planar (channel-major) components, inverting `to_planar`.
+    ///
+    /// The first axis must have one plane per component.
+    fn from_planar(planar: &Array3<T>) -> Array2<C>;
+}
@@ -0,0 +1,108 @@
+//! Demosaicing of raw Bayer-pattern sensor data.
+
+use ndarray::Array2;
+use num_traits::Float;
+
+use crate::{Components, components::cast};
+
+/// Layout of the colour filters in each `2 x 2` block of a Bayer sensor, read row by row from the top-left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BayerPattern {
+    /// Red and green above green and blue.
+    Rggb,
+    /// Blue and green above green and red.
+    Bggr,
+    /// Green and red above blue and green.
+    Grbg,
+    /// Green and blue above red and green.
+    Gbrg,
+}
+
+impl BayerPattern {
+    /// Colour channel (0 red, 1 green, 2 blue) sampled at a sensor position.
+    fn channel(self, row: usize, col: usize) -> usize {
+        let layout = match self {
+            BayerPattern::Rggb => [[0, 1], [1, 2]],
+            BayerPattern::Bggr => [[2, 1], [1, 0]],
+            BayerPattern::Grbg => [[1, 0], [2, 1]],
+            BayerPattern::Gbrg => [[1, 2], [0, 1]],
+        };
+        layout[row % 2][col % 2]
+    }
+}
+
+/// Demosaic a single-channel Bayer sensor array into a colour image by bilinear interpolation.
+///
+/// Each pixel keeps its own sampled channel, and the other two are averaged from the neighbouring sensor sites of that
+/// colour within its `3 x 3` neighbourhood. Raw values are scaled from the full 16-bit range to `[0, 1]`.
+pub fn debayer<C, T>(raw: &Array2<u16>, pattern: BayerPattern) -> Array2<C>
+where
+    C: Components<T, 3>,
+    T: Float + Send + Sync,
+{
+    let (h, w) = raw.dim();
+    let scale = cast::<T, _>(u16::MAX).recip();
+    Array2::from_shape_fn((h, w), |(row, col)| {
+        let mut sums = [T::zero(); 3];
+        let mut counts = [0usize; 3];
+        let own = pattern.channel(row, col);
+        for r in row.saturating_sub(1)..(row + 2).min(h) {
+            for c in col.saturating_sub(1)..(col + 2).min(w) {
+                let channel = pattern.channel(r, c);
+                if channel != own || (r == row && c == col) {
+                    sums[channel] = sums[channel] + cast(raw[[r, c]]);
+                    counts[channel] += 1;
+                }
+            }
+        }
+        C::from_components(std::array::from_fn(|n| {
+            if counts[n] == 0 {
+                T::zero()
+            } else {
+                sums[n] / cast(counts[n]) * scale
+            }
+        }))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chromatic::Rgb;
+    use ndarray::Array2;
+
+    use super::{BayerPattern, debayer};
+    use crate::Components;
+
+    #[test]
+    fn uniform_mosaics_reconstruct_their_colour_for_every_pattern() {
+        let colour = [0.8_f32, 0.4, 0.2];
+        for pattern in [BayerPattern::Rggb, BayerPattern::Bggr, BayerPattern::Grbg, BayerPattern::Gbrg] {
+            let raw = Array2::from_shape_fn((6, 8), |(row, col)| {
+                (colour[pattern.channel(row, col)] * f32::from(u16::MAX)).round() as u16
+            });
+
+            let image = debayer::<Rgb<f32>, f32>(&raw, pattern);
+            assert_eq!(image.dim(), (6, 8));
+            for pixel in &image {
+                let components = pixel.components();
+                assert!(
+                    components.iter().zip(colour).all(|(a, b)| (a - b).abs() < 1e-4),
+                    "{pattern:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn patterns_place_red_where_expected() {
+        let raw = Array2::from_shape_fn((2, 2), |(row, col)| if (row, col) == (0, 0) { u16::MAX } else { 0 });
+        assert_eq!(
+            debayer::<Rgb<f32>, f32>(&raw, BayerPattern::Rggb)[[0, 0]].components(),
+            [1.0, 0.0, 0.0]
+        );
+        assert_eq!(
+            debayer::<Rgb<f32>, f32>(&raw, BayerPattern::Bggr)[[0, 0]].components(),
+            [0.0, 0.0, 1.0]
+        );
+    }
+}
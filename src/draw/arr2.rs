@@ -0,0 +1,114 @@
+use ndarray::Array2;
+use num_traits::Float;
+
+use crate::{Components, Draw};
+
+impl<C, T, const N: usize> Draw<C, T, N> for Array2<C>
+where
+    C: Components<T, N> + Copy,
+    T: Float + Send + Sync,
+{
+    fn draw_disc(&mut self, centre: [i32; 2], diameter: usize, colour: C) {
+        if diameter == 0 {
+            return;
+        }
+
+        // Offsets are measured from the disc centre, which sits between pixels for even diameters
+        let radius = diameter as f64 / 2.0;
+        let offset = (diameter as i32 - 1) / 2;
+        let (h, w) = self.dim();
+        for dr in 0..diameter as i32 {
+            for dc in 0..diameter as i32 {
+                let (y, x) = (dr as f64 + 0.5 - radius, dc as f64 + 0.5 - radius);
+                if y * y + x * x > radius * radius {
+                    continue;
+                }
+                let (row, col) = (centre[0] + dr - offset, centre[1] + dc - offset);
+                if row >= 0 && col >= 0 && (row as usize) < h && (col as usize) < w {
+                    self[[row as usize, col as usize]] = colour;
+                }
+            }
+        }
+    }
+
+    fn draw_line(&mut self, start: [i32; 2], end: [i32; 2], thickness: usize, colour: C) {
+        // Bresenham's algorithm, stamping a disc at each step
+        let [mut row, mut col] = start;
+        let (dr, dc) = ((end[0] - row).abs(), -(end[1] - col).abs());
+        let (sr, sc) = ((end[0] - row).signum(), (end[1] - col).signum());
+        let mut err = dr + dc;
+        loop {
+            self.draw_disc([row, col], thickness, colour);
+            if [row, col] == end {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dc {
+                err += dc;
+                row += sr;
+            }
+            if e2 <= dr {
+                err += dr;
+                col += sc;
+            }
+        }
+    }
+
+    fn draw_polyline(&mut self, points: &[[i32; 2]], thickness: usize, colour: C) {
+        match points {
+            [] => {}
+            [point] => self.draw_disc(*point, thickness, colour),
+            _ => {
+                for segment in points.windows(2) {
+                    self.draw_line(segment[0], segment[1], thickness, colour);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chromatic::Grey;
+    use ndarray::Array2;
+
+    use crate::{Components, Draw};
+
+    fn lit(image: &Array2<Grey<f32>>) -> Vec<(usize, usize)> {
+        image
+            .indexed_iter()
+            .filter(|(_, pixel)| pixel.components()[0] > 0.5)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    #[test]
+    fn polyline_draws_both_segments_and_the_joint() {
+        let mut image = Array2::from_elem((10, 10), Grey::new(0.0_f32));
+        image.draw_polyline(&[[1, 1], [1, 8], [8, 8]], 1, Grey::new(1.0));
+
+        let mut expected = (1..=8)
+            .map(|col| (1, col))
+            .chain((2..=8).map(|row| (row, 8)))
+            .collect::<Vec<_>>();
+        expected.sort_unstable();
+        assert_eq!(lit(&image), expected);
+    }
+
+    #[test]
+    fn thick_polyline_fills_the_outside_of_the_joint() {
+        let mut image = Array2::from_elem((10, 10), Grey::new(0.0_f32));
+        image.draw_polyline(&[[1, 1], [1, 8], [8, 8]], 3, Grey::new(1.0));
+
+        let pixels = lit(&image);
+        assert!(pixels.contains(&(0, 9)) && pixels.contains(&(2, 7)));
+        assert!(!pixels.contains(&(4, 4)));
+    }
+
+    #[test]
+    fn drawing_is_clipped_to_the_image() {
+        let mut image = Array2::from_elem((4, 4), Grey::new(0.0_f32));
+        image.draw_line([-3, 1], [6, 1], 1, Grey::new(1.0));
+        assert_eq!(lit(&image), [(0, 1), (1, 1), (2, 1), (3, 1)]);
+    }
+}
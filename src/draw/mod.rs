@@ -0,0 +1,23 @@
+use num_traits::Float;
+
+use crate::Components;
+
+mod arr2;
+
+/// Trait for drawing primitive shapes onto an image.
+///
+/// Points are given as `[row, column]` and may lie outside the image, in which case drawing is clipped to its bounds.
+pub trait Draw<C, T, const N: usize>
+where
+    C: Components<T, N> + Clone,
+    T: Float + Send + Sync,
+{
+    /// Fill a disc of the given diameter centred on a point.
+    fn draw_disc(&mut self, centre: [i32; 2], diameter: usize, colour: C);
+
+    /// Draw a straight line of the given thickness between two points.
+    fn draw_line(&mut self, start: [i32; 2], end: [i32; 2], thickness: usize, colour: C);
+
+    /// Draw connected line segments of the given thickness through a sequence of points.
+    fn draw_polyline(&mut self, points: &[[i32; 2]], thickness: usize, colour: C);
+}
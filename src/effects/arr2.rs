@@ -0,0 +1,183 @@
+use ndarray::{Array2, Zip};
+use num_traits::Float;
+
+use crate::{
+    BorderMode, Components, Composite, Effects, Filter,
+    components::{cast, has_alpha, over, premultiply, unpremultiply},
+    dilate, distance_transform,
+    filter::gaussian,
+};
+
+impl<C, T, const N: usize> Effects<C, T, N> for Array2<C>
+where
+    C: Components<T, N> + Copy,
+    T: Float + Send + Sync,
+{
+    fn outline(&self, thickness: usize, colour: C) -> Self {
+        let mask = self.map(|pixel| !has_alpha(N) || pixel.components()[N - 1] > T::zero());
+        let ring = dilate(&mask, thickness);
+        Zip::from(self)
+            .and(&mask)
+            .and(&ring)
+            .map_collect(|&pixel, &inside, &ring| if ring && !inside { colour } else { pixel })
+    }
+
+    fn sdf_outline(&self, thickness: T, colour: C) -> Self {
+        if thickness <= T::zero() {
+            return self.clone();
+        }
+
+        // Distances are between pixel centres, so the region's edge lies half a pixel beyond its outermost centres
+        let opaque = self.map(|pixel| !has_alpha(N) || pixel.components()[N - 1] >= cast(0.5));
+        let distance = distance_transform::<T>(&opaque);
+        let tint = colour.components();
+        Zip::from(self).and(&distance).map_collect(|pixel, &d| {
+            let coverage = (thickness + T::one() - d).max(T::zero()).min(T::one());
+            if coverage == T::zero() {
+                return *pixel;
+            }
+            let mut border = tint;
+            if has_alpha(N) {
+                border[N - 1] = tint[N - 1] * coverage;
+            }
+            C::from_components(over(pixel.components(), border))
+        })
+    }
+
+    fn drop_shadow(&self, offset: [i32; 2], blur: T, colour: C) -> Self {
+        assert!(has_alpha(N), "Image must have an alpha channel");
+
+        // Grow the canvas to hold the image, the offset shadow and its blur margin
+        let (h, w) = self.dim();
+        let margin = (blur * cast(3)).ceil().to_isize().unwrap_or(0).max(0);
+        let span = |len: usize, offset: isize| {
+            let start = (offset - margin).min(0);
+            let end = (offset + len as isize + margin).max(len as isize);
+            (-start, (end - start) as usize)
+        };
+        let (top, height) = span(h, offset[0] as isize);
+        let (left, width) = span(w, offset[1] as isize);
+
+        // Stamp the shadow silhouette, blur it, then place the image over it
+        let tint = colour.components();
+        let silhouette = self.map(|pixel| {
+            let mut shadow = tint;
+            shadow[N - 1] = tint[N - 1] * pixel.components()[N - 1];
+            C::from_components(shadow)
+        });
+        let mut canvas = Array2::from_elem((height, width), C::from_components([T::zero(); N]));
+        canvas.paste(&silhouette, [top + offset[0] as isize, left + offset[1] as isize]);
+        let mut canvas = canvas.gaussian_blur(blur, BorderMode::Clamp);
+        canvas.paste_blend(self, [top, left]);
+        canvas
+    }
+
+    fn glow(&self, radius: T, colour: C, inner: bool) -> Self {
+        assert!(has_alpha(N), "Image must have an alpha channel");
+
+        // Blur the coverage on the side of the edge that should glow
+        let glow = colour.components();
+        let alpha = self.map(|pixel| pixel.components()[N - 1]);
+        let source = alpha.map(|&a| [if inner { T::one() - a } else { a }]);
+        let blurred = gaussian(&source, radius, BorderMode::Clamp);
+
+        Zip::from(self).and(&alpha).and(&blurred).map_collect(|pixel, &a, &[b]| {
+            let strength = if inner { b * a } else { b * (T::one() - a) } * glow[N - 1];
+            let mut out = premultiply(pixel.components());
+            if !inner {
+                out[N - 1] = a + strength * (T::one() - a);
+            }
+            for n in 0..N - 1 {
+                out[n] = out[n] + strength * glow[n];
+            }
+            let mut out = unpremultiply(out);
+            out[..N - 1]
+                .iter_mut()
+                .for_each(|value| *value = value.max(T::zero()).min(T::one()));
+            C::from_components(out)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chromatic::RgbAlpha;
+    use ndarray::{Array2, s};
+
+    use crate::{Components, Effects};
+
+    fn square() -> Array2<RgbAlpha<f32>> {
+        let mut image = Array2::from_elem((9, 9), RgbAlpha::new(0.0, 0.0, 0.0, 0.0));
+        image.slice_mut(s![3..6, 3..6]).fill(RgbAlpha::new(0.2, 0.4, 0.6, 1.0));
+        image
+    }
+
+    #[test]
+    fn outline_adds_a_ring_of_the_given_thickness() {
+        let red = RgbAlpha::new(1.0, 0.0, 0.0, 1.0);
+        let outlined = square().outline(2, red);
+
+        for ((row, col), pixel) in outlined.indexed_iter() {
+            let inside = (3..6).contains(&row) && (3..6).contains(&col);
+            let ring = (1..8).contains(&row) && (1..8).contains(&col);
+            let expected = if inside {
+                [0.2, 0.4, 0.6, 1.0]
+            } else if ring {
+                [1.0, 0.0, 0.0, 1.0]
+            } else {
+                [0.0; 4]
+            };
+            assert_eq!(pixel.components(), expected, "pixel ({row}, {col})");
+        }
+    }
+
+    #[test]
+    fn drop_shadow_sits_offset_and_soft_behind_the_image() {
+        let shadowed = square().drop_shadow([2, 3], 1.0, RgbAlpha::new(0.0, 0.0, 0.0, 1.0));
+        let alpha = |row: usize, col: usize| shadowed[[row, col]].components()[3];
+
+        // The blur margin reaches one row above the image, and the offset shadow extends past its bottom and right
+        assert_eq!(shadowed.dim(), (15, 15));
+        for row in 4..7 {
+            for col in 3..6 {
+                assert_eq!(shadowed[[row, col]].components(), [0.2, 0.4, 0.6, 1.0]);
+            }
+        }
+        assert!(alpha(7, 7) > 0.5 && shadowed[[7, 7]].components()[..3] == [0.0; 3]);
+        assert!(alpha(7, 9) > 0.0 && alpha(7, 9) < alpha(7, 7));
+        assert!(alpha(3, 2) < 1e-3);
+    }
+
+    #[test]
+    fn glow_brightens_just_outside_or_just_inside_the_edge() {
+        let white = RgbAlpha::new(1.0, 1.0, 1.0, 1.0);
+        let red = |image: &Array2<RgbAlpha<f32>>, row: usize, col: usize| image[[row, col]].components()[0];
+
+        let outer = square().glow(1.0, white, false);
+        let [r, g, b, a] = outer[[4, 2]].components();
+        assert!(a > 0.1 && r == 1.0 && g == 1.0 && b == 1.0);
+        assert!(outer[[4, 0]].components()[3] < a);
+        assert_eq!(outer[[4, 4]].components(), [0.2, 0.4, 0.6, 1.0]);
+
+        let inner = square().glow(1.0, white, true);
+        assert!(red(&inner, 4, 3) > red(&inner, 4, 4) && red(&inner, 4, 4) > 0.2);
+        assert_eq!(inner[[4, 2]].components(), [0.0; 4]);
+    }
+
+    #[test]
+    fn sdf_outline_draws_a_rounded_border_behind_the_image() {
+        let red = RgbAlpha::new(1.0, 0.0, 0.0, 1.0);
+        let outlined = square().sdf_outline(2.0, red);
+        let alpha = |row: usize, col: usize| outlined[[row, col]].components()[3];
+
+        assert_eq!(outlined[[4, 4]].components(), [0.2, 0.4, 0.6, 1.0]);
+        assert_eq!(outlined[[4, 1]].components(), [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(alpha(4, 0), 0.0);
+        // Corners are rounded, so the diagonal at distance 2.83 is only partly covered
+        assert!((alpha(1, 1) - (3.0 - 8.0_f32.sqrt())).abs() < 1e-4);
+        assert_eq!(alpha(0, 0), 0.0);
+
+        let unchanged = square().sdf_outline(0.0, red);
+        assert!(unchanged.iter().zip(&square()).all(|(a, b)| a.components() == b.components()));
+    }
+}
@@ -0,0 +1,42 @@
+use ndarray::Array2;
+use num_traits::Float;
+
+use crate::Components;
+
+mod arr2;
+
+/// Trait for stylistic effects built around an image's alpha channel.
+///
+/// Images without an alpha channel are treated as fully opaque.
+pub trait Effects<C, T, const N: usize>
+where
+    C: Components<T, N> + Clone,
+    T: Float + Send + Sync,
+{
+    /// Surround the opaque region with a solid ring of the given thickness, leaving existing pixels unchanged.
+    ///
+    /// The ring covers transparent pixels within `thickness` pixels (by a square neighbourhood) of a pixel with non-zero
+    /// alpha.
+    fn outline(&self, thickness: usize, colour: C) -> Array2<C>;
+
+    /// Draw a smooth border of the given thickness around the opaque region, behind the image.
+    ///
+    /// The border follows the Euclidean distance field of the pixels with at least half alpha, so corners are rounded,
+    /// and its coverage is anti-aliased over the last pixel. The image is composited over the border, and a thickness
+    /// of zero returns the image unchanged.
+    fn sdf_outline(&self, thickness: T, colour: C) -> Array2<C>;
+
+    /// Render a blurred, offset shadow of the alpha channel behind the image.
+    ///
+    /// The offset is given as `[row, column]`, and the shadow takes the given colour with its alpha scaled by the
+    /// image's alpha. The canvas grows to fit the shadow and its blur, with the original image composited on top.
+    /// The image must have an alpha channel.
+    fn drop_shadow(&self, offset: [i32; 2], blur: T, colour: C) -> Array2<C>;
+
+    /// Add a soft glow of the given colour just outside, or just inside, the edges of the opaque region.
+    ///
+    /// The glow strength comes from a Gaussian blur of the alpha channel with standard deviation `radius`, scaled by the
+    /// glow colour's alpha, and is added to the premultiplied colour. An outer glow also raises the alpha of the pixels
+    /// it covers. The image must have an alpha channel.
+    fn glow(&self, radius: T, colour: C, inner: bool) -> Array2<C>;
+}
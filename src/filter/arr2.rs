@@ -0,0 +1,953 @@
+use ndarray::{Array2, ArrayView2, Zip, s};
+use num_traits::Float;
+use std::{array, cmp::Ordering};
+
+use crate::{
+    BorderMode, Components, Filter,
+    components::{cast, colour_count, distance_squared, luma, premultiply, total_cmp, unpremultiply},
+};
+
+/// Colour sigma of the bilateral filter used to separate base and detail layers.
+const DETAIL_RANGE_SIGMA: f64 = 0.1;
+
+/// Log-luminance sigma of the bilateral filter used for tone mapping.
+const DURAND_RANGE_SIGMA: f64 = 0.4;
+
+/// Maximum number of mean-shift steps taken by each pixel.
+const MEAN_SHIFT_ITERATIONS: usize = 20;
+
+impl<C, T, const N: usize> Filter<C, T, N> for Array2<C>
+where
+    C: Components<T, N> + Copy,
+    T: Float + Send + Sync,
+{
+    fn gaussian_blur(&self, sigma: T, border: BorderMode) -> Self {
+        let premultiplied = self.map(|pixel| premultiply(pixel.components()));
+        gaussian(&premultiplied, sigma, border).map(|&c| C::from_components(unpremultiply(c)))
+    }
+
+    fn fast_gaussian_blur(&self, sigma: T, border: BorderMode) -> Self {
+        if self.is_empty() {
+            return self.clone();
+        }
+
+        let mut blurred = self.map(|pixel| premultiply(pixel.components()));
+        for radius in box_radii(sigma, 3) {
+            blurred = box_blur(&box_blur(&blurred, radius, border, false), radius, border, true);
+        }
+        blurred.map(|&c| C::from_components(unpremultiply(c)))
+    }
+
+    fn median_blur(&self, radius: usize, border: BorderMode) -> Self {
+        if radius == 0 || self.is_empty() {
+            return self.clone();
+        }
+
+        // Replace each component by its rank among the distinct values of that component in the image
+        let (h, w) = self.dim();
+        let components = self.map(|pixel| pixel.components());
+        let levels: [Vec<T>; N] = array::from_fn(|n| {
+            let mut values = components.iter().map(|c| c[n]).collect::<Vec<_>>();
+            values.sort_unstable_by(total_cmp);
+            values.dedup_by(|a, b| total_cmp(a, b).is_eq());
+            values
+        });
+        let ranks = components
+            .map(|c| array::from_fn::<_, N, _>(|n| levels[n].partition_point(|level| total_cmp(level, &c[n]).is_lt())));
+
+        let r = radius as isize;
+        let clamp = |i: isize, len: usize| border_index(border, i, len);
+        let half = (2 * radius + 1) * (2 * radius + 1) / 2;
+
+        // Allocate the counts once, emptying them again at the end of each row
+        let mut counts: [RankCounts; N] = array::from_fn(|n| RankCounts::new(levels[n].len()));
+        let mut out = Vec::with_capacity(h * w);
+        for row in 0..h {
+            let rows = (row as isize - r..=row as isize + r).map(|i| clamp(i, h)).collect::<Vec<_>>();
+            let update = |counts: &mut [RankCounts; N], col: usize, delta: isize| {
+                for &sr in &rows {
+                    for (count, &rank) in counts.iter_mut().zip(&ranks[[sr, col]]) {
+                        count.add(rank, delta);
+                    }
+                }
+            };
+
+            // Initial window, then slide one column at a time
+            for dc in -r..=r {
+                update(&mut counts, clamp(dc, w), 1);
+            }
+            for col in 0..w {
+                if col > 0 {
+                    update(&mut counts, clamp(col as isize - r - 1, w), -1);
+                    update(&mut counts, clamp(col as isize + r, w), 1);
+                }
+                out.push(C::from_components(array::from_fn(|n| levels[n][counts[n].nth(half)])));
+            }
+            for dc in -r..=r {
+                update(&mut counts, clamp(w as isize - 1 + dc, w), -1);
+            }
+        }
+
+        Array2::from_shape_vec((h, w), out).expect("Output length matches image size")
+    }
+
+    fn kuwahara(&self, radius: usize) -> Self {
+        if radius == 0 {
+            return self.clone();
+        }
+        let (h, w) = self.dim();
+        let premultiplied = self.map(|pixel| premultiply(pixel.components()));
+        let lumas = premultiplied.map(luma);
+        let count: T = cast((radius + 1) * (radius + 1));
+
+        Array2::from_shape_fn((h, w), |(row, col)| {
+            // Summarise each quadrant by its mean colour and luminance variance
+            let quadrants = [[-1, -1], [-1, 1], [1, -1], [1, 1]].map(|[dy, dx]: [isize; 2]| {
+                let (mut sum, mut luma_sum, mut luma_squares) = ([T::zero(); N], T::zero(), T::zero());
+                for i in 0..=radius as isize {
+                    let r = border_index(BorderMode::Clamp, row as isize + dy * i, h);
+                    for j in 0..=radius as isize {
+                        let c = border_index(BorderMode::Clamp, col as isize + dx * j, w);
+                        for (s, &v) in sum.iter_mut().zip(&premultiplied[[r, c]]) {
+                            *s = *s + v;
+                        }
+                        luma_sum = luma_sum + lumas[[r, c]];
+                        luma_squares = luma_squares + lumas[[r, c]] * lumas[[r, c]];
+                    }
+                }
+                let luma_mean = luma_sum / count;
+                (sum.map(|s| s / count), luma_squares / count - luma_mean * luma_mean)
+            });
+
+            let (mean, _) = quadrants
+                .into_iter()
+                .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal))
+                .expect("There are always four quadrants");
+            C::from_components(unpremultiply(mean))
+        })
+    }
+
+    fn map_with_neighbours<F>(&self, radius: usize, border: BorderMode, f: F) -> Self
+    where
+        F: Fn(ArrayView2<C>) -> C,
+    {
+        let (h, w) = self.dim();
+        if h == 0 || w == 0 {
+            return self.clone();
+        }
+
+        // Pad the image once so every window is a plain view
+        let r = radius as isize;
+        let padded = Array2::from_shape_fn((h + 2 * radius, w + 2 * radius), |(row, col)| {
+            self[[
+                border_index(border, row as isize - r, h),
+                border_index(border, col as isize - r, w),
+            ]]
+        });
+        Array2::from_shape_fn((h, w), |(row, col)| {
+            f(padded.slice(s![row..=row + 2 * radius, col..=col + 2 * radius]))
+        })
+    }
+
+    fn sobel(&self) -> Array2<T> {
+        let (h, w) = self.dim();
+        let luma = self.map(|pixel| luma(&pixel.components()));
+        let at = |row: usize, col: usize, dr: isize, dc: isize| {
+            let r = (row as isize + dr).clamp(0, h as isize - 1) as usize;
+            let c = (col as isize + dc).clamp(0, w as isize - 1) as usize;
+            luma[[r, c]]
+        };
+
+        let two = cast::<T, _>(2);
+        Array2::from_shape_fn((h, w), |(row, col)| {
+            let p = |dr, dc| at(row, col, dr, dc);
+            let gx = (p(-1, 1) + two * p(0, 1) + p(1, 1)) - (p(-1, -1) + two * p(0, -1) + p(1, -1));
+            let gy = (p(1, -1) + two * p(1, 0) + p(1, 1)) - (p(-1, -1) + two * p(-1, 0) + p(-1, 1));
+            gx.hypot(gy)
+        })
+    }
+
+    fn bilateral_filter(&self, spatial_sigma: T, range_sigma: T) -> Self {
+        let components = self.map(Components::components);
+        joint_bilateral(&components, Some(&components), spatial_sigma, range_sigma).map(|&c| C::from_components(c))
+    }
+
+    fn rolling_guidance_filter(&self, spatial_sigma: T, range_sigma: T, iterations: usize) -> Self {
+        let components = self.map(Components::components);
+        let mut guide = None;
+        for _ in 0..iterations {
+            guide = Some(joint_bilateral(&components, guide.as_ref(), spatial_sigma, range_sigma));
+        }
+        guide.map_or_else(|| self.clone(), |guide| guide.map(|&c| C::from_components(c)))
+    }
+
+    fn enhance_detail(&self, sigma: T, factor: T) -> Self {
+        let components = self.map(Components::components);
+        let base = joint_bilateral(&components, Some(&components), sigma, cast(DETAIL_RANGE_SIGMA));
+        Zip::from(&components).and(&base).map_collect(|input, base| {
+            let mut out = *input;
+            for n in 0..colour_count(N) {
+                out[n] = input[n] + (input[n] - base[n]) * factor;
+            }
+            C::from_components(out)
+        })
+    }
+
+    fn unsharp_mask(&self, sigma: T, amount: T) -> Self {
+        unsharp(self, sigma, amount, |_| true)
+    }
+
+    fn sharpen_masked(&self, sigma: T, amount: T, edge_threshold: T) -> Self {
+        let edges = self.sobel();
+        unsharp(self, sigma, amount, |index| edges[index] > edge_threshold)
+    }
+
+    fn decompose_base_detail(&self, sigma: T) -> (Self, Self) {
+        let components = self.map(Components::components);
+        let base = joint_bilateral(&components, Some(&components), sigma, cast(DETAIL_RANGE_SIGMA));
+        let half: T = cast(0.5);
+        let detail = Zip::from(&components).and(&base).map_collect(|input, base| {
+            let mut out = [T::one(); N];
+            for n in 0..colour_count(N) {
+                out[n] = half + (input[n] - base[n]) * half;
+            }
+            C::from_components(out)
+        });
+        let base = Zip::from(&components).and(&base).map_collect(|input, base| {
+            let mut out = *base;
+            out[colour_count(N)..].copy_from_slice(&input[colour_count(N)..]);
+            C::from_components(out)
+        });
+        (base, detail)
+    }
+
+    fn tonemap_durand(&self, contrast: T) -> Self {
+        let (h, w) = self.dim();
+        if h == 0 || w == 0 {
+            return self.clone();
+        }
+
+        // Decompose log luminance, with a spatial scale proportional to the image size
+        let epsilon: T = cast(1e-6);
+        let luminance = self.map(|pixel| luma(&pixel.components()).max(epsilon));
+        let log = luminance.map(|l| [l.ln()]);
+        let sigma = cast::<T, _>(h.max(w)) * cast(0.02);
+        let base = joint_bilateral(&log, Some(&log), sigma.max(T::one()), cast(DURAND_RANGE_SIGMA));
+
+        // Compress the base layer's range to the target contrast
+        let (min, max) = base.iter().fold((T::infinity(), T::neg_infinity()), |(min, max), &[b]| {
+            (min.min(b), max.max(b))
+        });
+        let scale = if max > min { contrast.ln() / (max - min) } else { T::one() };
+
+        Zip::from(self)
+            .and(&luminance)
+            .and(&log)
+            .and(&base)
+            .map_collect(|pixel, &luminance, &[log], &[base]| {
+                let target = ((base - max) * scale + (log - base)).exp();
+                let mut out = pixel.components();
+                for value in out.iter_mut().take(colour_count(N)) {
+                    *value = (*value * target / luminance).max(T::zero()).min(T::one());
+                }
+                C::from_components(out)
+            })
+    }
+
+    fn guided_filter(&self, guide: &Self, radius: usize, epsilon: T) -> Self {
+        assert_eq!(self.dim(), guide.dim(), "Guide must have the same dimensions as the image");
+
+        let guide = guide.map(|pixel| luma(&pixel.components()));
+        let mean_i = box_mean(&guide, radius);
+        let var_i = box_mean(&guide.map(|&i| i * i), radius) - &mean_i * &mean_i;
+        let components = self.map(Components::components);
+
+        let mut out = components.clone();
+        for n in 0..N {
+            let p = components.map(|c| c[n]);
+            let mean_p = box_mean(&p, radius);
+            let cov_ip = box_mean(&(&guide * &p), radius) - &mean_i * &mean_p;
+            let a = Zip::from(&cov_ip).and(&var_i).map_collect(|&cov, &var| cov / (var + epsilon));
+            let b = &mean_p - &(&a * &mean_i);
+            let (mean_a, mean_b) = (box_mean(&a, radius), box_mean(&b, radius));
+            Zip::from(&mut out)
+                .and(&guide)
+                .and(&mean_a)
+                .and(&mean_b)
+                .for_each(|out, &i, &a, &b| out[n] = a * i + b);
+        }
+
+        out.map(|&c| C::from_components(c))
+    }
+
+    fn recompose_base_detail(base: &Self, detail: &Self) -> Self {
+        assert_eq!(
+            base.dim(),
+            detail.dim(),
+            "Base and detail layers must have matching dimensions"
+        );
+        let two: T = cast(2);
+        Zip::from(base).and(detail).map_collect(|base, detail| {
+            let (mut out, detail) = (base.components(), detail.components());
+            for n in 0..colour_count(N) {
+                out[n] = out[n] + detail[n] * two - T::one();
+            }
+            C::from_components(out)
+        })
+    }
+
+    fn mean_shift_segment(&self, spatial_radius: usize, colour_radius: T) -> Self {
+        let (h, w) = self.dim();
+        let colours = colour_count(N);
+        let components = self.map(|pixel| pixel.components());
+        let radius_squared = colour_radius * colour_radius;
+        let colour_distance =
+            |a: &[T; N], b: &[T; N]| (0..colours).fold(T::zero(), |acc, n| acc + (a[n] - b[n]) * (a[n] - b[n]));
+        let tolerance = cast::<T, _>(1e-3) * radius_squared;
+
+        // Move each pixel to the mode of its joint spatial-colour neighbourhood
+        let modes = Array2::from_shape_fn((h, w), |(row, col)| {
+            let (mut y, mut x) = (cast::<T, _>(row), cast::<T, _>(col));
+            let mut colour = components[[row, col]];
+            for _ in 0..MEAN_SHIFT_ITERATIONS {
+                let (cy, cx) = (y.round().to_usize().unwrap_or(0), x.round().to_usize().unwrap_or(0));
+                let (mut sy, mut sx, mut sum, mut count) = (T::zero(), T::zero(), [T::zero(); N], 0);
+                for r in cy.saturating_sub(spatial_radius)..(cy + spatial_radius + 1).min(h) {
+                    for c in cx.saturating_sub(spatial_radius)..(cx + spatial_radius + 1).min(w) {
+                        let other = &components[[r, c]];
+                        if colour_distance(other, &colour) <= radius_squared {
+                            sy = sy + cast(r);
+                            sx = sx + cast(c);
+                            for (s, &v) in sum.iter_mut().zip(other) {
+                                *s = *s + v;
+                            }
+                            count += 1;
+                        }
+                    }
+                }
+                if count == 0 {
+                    break;
+                }
+
+                let scale = cast::<T, _>(count).recip();
+                let next = sum.map(|s| s * scale);
+                let (ny, nx) = (sy * scale, sx * scale);
+                let shift = (ny - y) * (ny - y) + (nx - x) * (nx - x) + colour_distance(&next, &colour);
+                (y, x, colour) = (ny, nx, next);
+                if shift < tolerance {
+                    break;
+                }
+            }
+            colour
+        });
+
+        // Merge neighbouring pixels with similar modes into regions sharing their mean mode
+        let mut out = components.clone();
+        let mut visited = Array2::from_elem((h, w), false);
+        for start in (0..h).flat_map(|row| (0..w).map(move |col| [row, col])) {
+            if visited[start] {
+                continue;
+            }
+            visited[start] = true;
+            let mut region = vec![start];
+            let mut next = 0;
+            while next < region.len() {
+                let [r, c] = region[next];
+                next += 1;
+                let neighbours = [[r.wrapping_sub(1), c], [r + 1, c], [r, c.wrapping_sub(1)], [r, c + 1]];
+                for neighbour in neighbours {
+                    if neighbour[0] < h
+                        && neighbour[1] < w
+                        && !visited[neighbour]
+                        && colour_distance(&modes[neighbour], &modes[[r, c]]) < radius_squared
+                    {
+                        visited[neighbour] = true;
+                        region.push(neighbour);
+                    }
+                }
+            }
+
+            let mut mean = [T::zero(); N];
+            for &index in &region {
+                for (m, &v) in mean.iter_mut().zip(&modes[index]) {
+                    *m = *m + v;
+                }
+            }
+            let mean = mean.map(|m| m / cast(region.len()));
+            for index in region {
+                out[index][..colours].copy_from_slice(&mean[..colours]);
+            }
+        }
+
+        out.map(|&c| C::from_components(c))
+    }
+}
+
+/// Helper function to unsharp mask the colour components of the pixels selected by `mask`.
+fn unsharp<C, T, const N: usize>(image: &Array2<C>, sigma: T, amount: T, mask: impl Fn([usize; 2]) -> bool) -> Array2<C>
+where
+    C: Components<T, N> + Copy,
+    T: Float + Send + Sync,
+{
+    let components = image.map(|pixel| pixel.components());
+    let blurred = gaussian(&components, sigma, BorderMode::Clamp);
+    Array2::from_shape_fn(image.dim(), |(row, col)| {
+        let (input, blurred) = (components[[row, col]], blurred[[row, col]]);
+        let mut out = input;
+        if mask([row, col]) {
+            for n in 0..colour_count(N) {
+                out[n] = input[n] + (input[n] - blurred[n]) * amount;
+            }
+        }
+        C::from_components(out)
+    })
+}
+
+/// Helper function to compute the mean of each `(2r + 1) x (2r + 1)` window, clipped to the field, using an integral
+/// image.
+fn box_mean<T: Float>(field: &Array2<T>, radius: usize) -> Array2<T> {
+    let (h, w) = field.dim();
+    let mut integral = Array2::zeros((h + 1, w + 1));
+    for row in 0..h {
+        for col in 0..w {
+            integral[[row + 1, col + 1]] =
+                field[[row, col]] + integral[[row, col + 1]] + integral[[row + 1, col]] - integral[[row, col]];
+        }
+    }
+
+    Array2::from_shape_fn((h, w), |(row, col)| {
+        let (r0, r1) = (row.saturating_sub(radius), (row + radius + 1).min(h));
+        let (c0, c1) = (col.saturating_sub(radius), (col + radius + 1).min(w));
+        let sum = integral[[r1, c1]] - integral[[r0, c1]] - integral[[r1, c0]] + integral[[r0, c0]];
+        sum / cast((r1 - r0) * (c1 - c0))
+    })
+}
+
+/// Helper function to map a possibly out-of-range index along an axis of length `len` back into range.
+fn border_index(border: BorderMode, i: isize, len: usize) -> usize {
+    match border {
+        BorderMode::Clamp => i.clamp(0, len as isize - 1) as usize,
+        BorderMode::Wrap => i.rem_euclid(len as isize) as usize,
+    }
+}
+
+/// Helper function to find the radii of `passes` successive box blurs whose combined variance approximates `sigma`.
+fn box_radii<T: Float>(sigma: T, passes: usize) -> Vec<usize> {
+    let variance = (sigma * sigma * cast(12)).to_f64().unwrap_or(0.0);
+    let n = passes as f64;
+    let ideal = (variance / n + 1.0).sqrt();
+    let mut lower = ideal.floor() as usize;
+    if lower.is_multiple_of(2) {
+        lower = lower.saturating_sub(1);
+    }
+    let lower = lower.max(1);
+    let l = lower as f64;
+    let wide = ((variance - n * l * l - 4.0 * n * l - 3.0 * n) / (-4.0 * l - 4.0))
+        .round()
+        .clamp(0.0, n) as usize;
+    (0..passes)
+        .map(|pass| if pass < wide { (lower - 1) / 2 } else { lower.div_ceil(2) })
+        .collect()
+}
+
+/// Helper function to average each `2r + 1` window along one axis using a running sum.
+fn box_blur<T: Float, const N: usize>(
+    input: &Array2<[T; N]>,
+    radius: usize,
+    border: BorderMode,
+    vertical: bool,
+) -> Array2<[T; N]> {
+    if radius == 0 {
+        return input.clone();
+    }
+
+    let (h, w) = input.dim();
+    let (lines, len) = if vertical { (w, h) } else { (h, w) };
+    let at = |line: usize, i: isize| {
+        let i = border_index(border, i, len);
+        if vertical { input[[i, line]] } else { input[[line, i]] }
+    };
+    let r = radius as isize;
+    let scale = cast::<T, _>(2 * radius + 1).recip();
+
+    let mut out = Array2::from_elem((h, w), [T::zero(); N]);
+    for line in 0..lines {
+        let mut sum = [T::zero(); N];
+        for i in -r..=r {
+            for (s, v) in sum.iter_mut().zip(at(line, i)) {
+                *s = *s + v;
+            }
+        }
+        for i in 0..len {
+            if i > 0 {
+                let (leaving, entering) = (at(line, i as isize - r - 1), at(line, i as isize + r));
+                for ((s, l), e) in sum.iter_mut().zip(leaving).zip(entering) {
+                    *s = *s - l + e;
+                }
+            }
+            let index = if vertical { [i, line] } else { [line, i] };
+            out[index] = sum.map(|s| s * scale);
+        }
+    }
+    out
+}
+
+/// Helper function to blur component arrays with a separable Gaussian kernel.
+pub(crate) fn gaussian<T: Float, const N: usize>(input: &Array2<[T; N]>, sigma: T, border: BorderMode) -> Array2<[T; N]> {
+    let radius = (sigma * cast(3)).ceil().to_usize().unwrap_or(0);
+    if radius == 0 || input.is_empty() {
+        return input.clone();
+    }
+
+    // Normalised one-dimensional kernel
+    let scale = -(sigma * sigma * cast(2)).recip();
+    let mut kernel = (0..=2 * radius)
+        .map(|i| {
+            let d = cast::<T, _>(i as isize - radius as isize);
+            (d * d * scale).exp()
+        })
+        .collect::<Vec<_>>();
+    let total = kernel.iter().fold(T::zero(), |acc, &k| acc + k);
+    kernel.iter_mut().for_each(|k| *k = *k / total);
+
+    let (h, w) = input.dim();
+    let convolve = |src: &Array2<[T; N]>, vertical: bool| {
+        Array2::from_shape_fn((h, w), |(row, col)| {
+            let mut sum = [T::zero(); N];
+            for (i, &k) in kernel.iter().enumerate() {
+                let offset = i as isize - radius as isize;
+                let value = if vertical {
+                    &src[[border_index(border, row as isize + offset, h), col]]
+                } else {
+                    &src[[row, border_index(border, col as isize + offset, w)]]
+                };
+                for (s, &v) in sum.iter_mut().zip(value) {
+                    *s = *s + v * k;
+                }
+            }
+            sum
+        })
+    };
+
+    convolve(&convolve(input, false), true)
+}
+
+/// Helper function to apply a joint bilateral filter to component arrays, with colour weights taken from the guide.
+///
+/// Without a guide all colour weights are equal, giving a Gaussian blur. A zero spatial sigma returns the input, and a
+/// zero range sigma only averages neighbours whose guide colour exactly matches the centre's.
+fn joint_bilateral<T: Float, const N: usize>(
+    input: &Array2<[T; N]>,
+    guide: Option<&Array2<[T; N]>>,
+    spatial_sigma: T,
+    range_sigma: T,
+) -> Array2<[T; N]> {
+    let radius = (spatial_sigma * cast(3)).ceil().to_isize().unwrap_or(0).max(0);
+    if radius == 0 || input.is_empty() {
+        return input.clone();
+    }
+
+    let (h, w) = input.dim();
+    let spatial_scale = -(spatial_sigma * spatial_sigma * cast(2)).recip();
+    let range_scale = -(range_sigma * range_sigma * cast(2)).recip();
+    let range_weight = |distance: T| {
+        if range_sigma > T::zero() {
+            (distance * range_scale).exp()
+        } else if distance == T::zero() {
+            T::one()
+        } else {
+            T::zero()
+        }
+    };
+    let clamp = |i: isize, len: usize| i.clamp(0, len as isize - 1) as usize;
+
+    // Spatial weights depend only on the offset, so compute them once
+    let kernel = Array2::from_shape_fn(((2 * radius + 1) as usize, (2 * radius + 1) as usize), |(i, j)| {
+        let (dr, dc) = (cast::<T, _>(i as isize - radius), cast::<T, _>(j as isize - radius));
+        ((dr * dr + dc * dc) * spatial_scale).exp()
+    });
+
+    Array2::from_shape_fn((h, w), |(row, col)| {
+        let mut sum = [T::zero(); N];
+        let mut total = T::zero();
+        for ((i, j), &spatial) in kernel.indexed_iter() {
+            let r = clamp(row as isize + i as isize - radius, h);
+            let c = clamp(col as isize + j as isize - radius, w);
+            let weight = match guide {
+                Some(guide) => spatial * range_weight(distance_squared(&guide[[row, col]], &guide[[r, c]])),
+                None => spatial,
+            };
+            for (s, &v) in sum.iter_mut().zip(&input[[r, c]]) {
+                *s = *s + v * weight;
+            }
+            total = total + weight;
+        }
+        sum.map(|s| s / total)
+    })
+}
+
+/// Counts of component ranks within a sliding window, held as a Fenwick tree so ranks can be added, removed and
+/// selected in logarithmic time.
+struct RankCounts {
+    tree: Vec<isize>,
+}
+
+impl RankCounts {
+    /// Create empty counts for ranks below `len`.
+    fn new(len: usize) -> Self {
+        Self { tree: vec![0; len + 1] }
+    }
+
+    /// Change the count of a rank by `delta`.
+    fn add(&mut self, rank: usize, delta: isize) {
+        let mut i = rank + 1;
+        while i < self.tree.len() {
+            self.tree[i] += delta;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// Find the rank at position `index` when the counted ranks are sorted.
+    fn nth(&self, index: usize) -> usize {
+        let (mut position, mut remaining) = (0, index as isize);
+        let mut step = (self.tree.len() - 1).next_power_of_two();
+        while step > 0 {
+            let next = position + step;
+            if next < self.tree.len() && self.tree[next] <= remaining {
+                position = next;
+                remaining -= self.tree[next];
+            }
+            step /= 2;
+        }
+        position
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chromatic::{Grey, Rgb, RgbAlpha};
+    use ndarray::{Array2, ArrayView2};
+
+    use crate::{BorderMode, Components, Filter};
+
+    #[test]
+    fn median_blur_removes_isolated_outliers() {
+        let mut image = Array2::from_elem((5, 5), Rgb::new(0.2_f32, 0.4, 0.6));
+        image[[2, 2]] = Rgb::new(1.0, 0.0, 1.0);
+        image[[0, 4]] = Rgb::new(0.0, 1.0, 0.0);
+
+        let blurred = image.median_blur(1, BorderMode::Clamp);
+        assert!(blurred.iter().all(|pixel| pixel.components() == [0.2, 0.4, 0.6]));
+    }
+
+    #[test]
+    fn median_blur_keeps_full_precision() {
+        let values = [0.5, 0.501, 0.502, 0.503, 0.504, 0.505, 0.506, 0.507, 0.508];
+        let image = Array2::from_shape_fn((3, 3), |(row, col)| Grey::new(values[row * 3 + col]));
+
+        let blurred = image.median_blur(1, BorderMode::Clamp);
+        assert_eq!(blurred[[1, 1]].components(), [0.504_f64]);
+    }
+
+    #[test]
+    fn median_blur_with_zero_radius_is_identity() {
+        let image = Array2::from_shape_fn((2, 3), |(row, col)| Grey::new((row * 3 + col) as f32 / 5.0));
+        let blurred = image.median_blur(0, BorderMode::Clamp);
+        assert!(blurred.iter().zip(&image).all(|(a, b)| a.components() == b.components()));
+    }
+
+    #[test]
+    fn base_and_detail_recompose_to_the_original() {
+        let image = Array2::from_shape_fn((8, 8), |(row, col)| {
+            let edge = if col < 4 { 0.1_f32 } else { 0.9 };
+            let texture = if (row + col) % 2 == 0 { 0.05 } else { -0.05 };
+            RgbAlpha::new(edge + texture, 0.5 - texture, edge, 0.75)
+        });
+
+        let (base, detail) = image.decompose_base_detail(2.0);
+        let recomposed = Array2::recompose_base_detail(&base, &detail);
+        for (a, b) in recomposed.iter().zip(&image) {
+            assert!(a.components().iter().zip(b.components()).all(|(a, b)| (a - b).abs() < 1e-5));
+        }
+        assert!(base.iter().all(|pixel| pixel.components()[3] == 0.75));
+    }
+
+    #[test]
+    fn sobel_responds_only_at_edges_with_clamped_borders() {
+        let image = Array2::from_shape_fn((4, 6), |(_, col)| Grey::new(if col < 3 { 0.0_f32 } else { 1.0 }));
+
+        let magnitude = image.sobel();
+        assert_eq!(magnitude.dim(), (4, 6));
+        for row in 0..4 {
+            assert_eq!(magnitude.row(row).to_vec(), [0.0, 0.0, 4.0, 4.0, 0.0, 0.0]);
+        }
+    }
+
+    #[test]
+    fn rolling_guidance_removes_texture_but_keeps_edges() {
+        // Fine checkerboard texture over a strong vertical edge
+        let image = Array2::from_shape_fn((16, 16), |(row, col)| {
+            let base = if col < 8 { 0.2_f32 } else { 0.8 };
+            let texture = if (row + col).is_multiple_of(2) { 0.05 } else { -0.05 };
+            Grey::new(base + texture)
+        });
+
+        let smoothed = image.rolling_guidance_filter(2.0, 0.15, 4);
+        let value = |row: usize, col: usize| smoothed[[row, col]].components()[0];
+        let interior = (2..6)
+            .chain(10..14)
+            .map(|col| (value(8, col) - value(8, col + 1)).abs())
+            .fold(0.0, f32::max);
+        assert!(interior < 0.01, "texture remains: {interior}");
+        assert!(value(8, 8) - value(8, 7) > 0.5, "edge blurred");
+    }
+
+    #[test]
+    fn enhance_detail_boosts_texture_without_halos() {
+        let texture = Array2::from_shape_fn((8, 8), |(row, col)| {
+            Grey::new(if (row + col).is_multiple_of(2) { 0.52_f32 } else { 0.48 })
+        });
+        let enhanced = texture.enhance_detail(1.5, 2.0);
+        let contrast = (enhanced[[4, 4]].components()[0] - enhanced[[4, 5]].components()[0]).abs();
+        assert!(contrast > 0.1, "contrast only {contrast}");
+
+        let step = Array2::from_shape_fn((8, 8), |(_, col)| Grey::new(if col < 4 { 0.2_f32 } else { 0.8 }));
+        let enhanced = step.enhance_detail(1.5, 2.0);
+        assert!(enhanced.iter().all(|pixel| (0.19..=0.81).contains(&pixel.components()[0])));
+
+        let ramp = Array2::from_shape_fn((8, 8), |(_, col)| Grey::new(0.4 + col as f32 * 0.01));
+        let enhanced = ramp.enhance_detail(1.5, 2.0);
+        for col in 2..6 {
+            assert!((enhanced[[4, col]].components()[0] - ramp[[4, col]].components()[0]).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn tonemap_durand_compresses_range_and_keeps_texture() {
+        // Textured shadows and highlights with a dynamic range of several hundred to one
+        let image = Array2::from_shape_fn((32, 32), |(row, col)| {
+            let base = if col < 16 { 0.001_f32 } else { 0.7 };
+            Grey::new(if (row + col).is_multiple_of(2) {
+                base * 1.2
+            } else {
+                base / 1.2
+            })
+        });
+
+        let mapped = image.tonemap_durand(10.0);
+        let value = |row: usize, col: usize| mapped[[row, col]].components()[0];
+        assert!(mapped.iter().all(|pixel| (0.0..=1.0).contains(&pixel.components()[0])));
+        assert!(value(16, 8) > 0.05, "shadows stay crushed");
+        assert!((value(16, 8) - value(16, 9)).abs() > 0.01, "shadow texture lost");
+        assert!((value(16, 24) - value(16, 25)).abs() > 0.05, "highlight texture lost");
+    }
+
+    #[test]
+    fn gaussian_blur_does_not_darken_edges_against_transparency() {
+        let orange = RgbAlpha::new(1.0_f32, 0.5, 0.0, 1.0);
+        let image = Array2::from_shape_fn((9, 9), |(row, col)| {
+            if (3..6).contains(&row) && (3..6).contains(&col) {
+                orange
+            } else {
+                RgbAlpha::new(0.0, 0.0, 0.0, 0.0)
+            }
+        });
+
+        for blurred in [
+            image.gaussian_blur(1.0, BorderMode::Clamp),
+            image.fast_gaussian_blur(1.0, BorderMode::Clamp),
+        ] {
+            let [red, green, blue, alpha] = blurred[[4, 2]].components();
+            assert!(alpha > 0.0 && alpha < 0.5);
+            assert!((red - 1.0).abs() < 1e-5 && (green - 0.5).abs() < 1e-5 && blue.abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn self_guided_filter_smooths_noise_and_keeps_edges() {
+        let image = Array2::from_shape_fn((16, 32), |(row, col)| {
+            let base = if col < 16 { 0.2_f32 } else { 0.8 };
+            Grey::new(if (row * 3 + col).is_multiple_of(2) {
+                base + 0.02
+            } else {
+                base - 0.02
+            })
+        });
+
+        for radius in [2, 6] {
+            let filtered = image.guided_filter(&image, radius, 0.01);
+            let value = |row: usize, col: usize| filtered[[row, col]].components()[0];
+            assert!((value(8, 2) - value(8, 3)).abs() < 0.01, "noise remains at radius {radius}");
+            assert!(value(8, 16) - value(8, 15) > 0.4, "edge lost at radius {radius}");
+        }
+    }
+
+    #[test]
+    fn wrap_border_blurs_across_opposite_edges() {
+        let image = Array2::from_shape_fn((6, 8), |(_, col)| Grey::new(if col == 0 { 1.0_f32 } else { 0.0 }));
+        let value = |image: &Array2<Grey<f32>>, col: usize| image[[3, col]].components()[0];
+
+        for blurred in [
+            image.gaussian_blur(1.0, BorderMode::Wrap),
+            image.fast_gaussian_blur(2.0, BorderMode::Wrap),
+        ] {
+            assert!(value(&blurred, 7) > 0.0);
+            assert!((value(&blurred, 1) - value(&blurred, 7)).abs() < 1e-6);
+        }
+        let clamped = image.gaussian_blur(1.0, BorderMode::Clamp);
+        assert!(value(&clamped, 7) < 1e-3);
+
+        // Only wrapping brings the bright last column into the first column's window
+        let columns = Array2::from_shape_fn((5, 5), |(_, col)| Grey::new(if col == 1 || col == 4 { 1.0_f32 } else { 0.0 }));
+        assert_eq!(value(&columns.median_blur(1, BorderMode::Wrap), 0), 1.0);
+        assert_eq!(value(&columns.median_blur(1, BorderMode::Clamp), 0), 0.0);
+    }
+
+    #[test]
+    fn fast_gaussian_blur_approximates_the_true_blur() {
+        let image = Array2::from_shape_fn((40, 40), |(row, col)| {
+            Grey::new(if (row / 5 + col / 7).is_multiple_of(2) { 0.9_f32 } else { 0.1 })
+        });
+
+        let exact = image.gaussian_blur(4.0, BorderMode::Wrap);
+        let fast = image.fast_gaussian_blur(4.0, BorderMode::Wrap);
+        let error = exact
+            .iter()
+            .zip(&fast)
+            .map(|(a, b)| (a.components()[0] - b.components()[0]).abs())
+            .fold(0.0, f32::max);
+        assert!(error < 0.03, "maximum error {error}");
+    }
+
+    #[test]
+    fn sharpen_masked_sharpens_edges_and_leaves_flat_noise() {
+        let image = Array2::from_shape_fn((12, 12), |(row, col)| {
+            let base = if col < 6 { 0.3_f32 } else { 0.7 };
+            Grey::new(if (row + col).is_multiple_of(2) {
+                base + 0.01
+            } else {
+                base - 0.01
+            })
+        });
+        let value = |image: &Array2<Grey<f32>>, row: usize, col: usize| image[[row, col]].components()[0];
+
+        let sharpened = image.sharpen_masked(1.0, 1.0, 0.5);
+        assert_eq!(value(&sharpened, 6, 1), value(&image, 6, 1));
+        assert_eq!(value(&sharpened, 6, 10), value(&image, 6, 10));
+        assert!(value(&sharpened, 6, 5) < value(&image, 6, 5) - 0.05);
+        assert!(value(&sharpened, 6, 6) > value(&image, 6, 6) + 0.05);
+
+        let everywhere = image.unsharp_mask(1.0, 1.0);
+        assert_ne!(value(&everywhere, 6, 1), value(&image, 6, 1));
+    }
+
+    #[test]
+    fn mean_shift_flattens_noise_into_regions_split_at_strong_edges() {
+        let image = Array2::from_shape_fn((10, 12), |(row, col)| {
+            let base = if col < 6 { 0.2_f32 } else { 0.8 };
+            let noise = ((row * 5 + col * 3) % 7) as f32 / 6.0 * 0.06 - 0.03;
+            Rgb::new(base + noise, base, base - noise)
+        });
+
+        let segmented = image.mean_shift_segment(3, 0.15);
+        let left = segmented[[0, 0]].components();
+        let right = segmented[[0, 11]].components();
+        for ((_, col), pixel) in segmented.indexed_iter() {
+            assert_eq!(pixel.components(), if col < 6 { left } else { right });
+        }
+        assert!((left[1] - 0.2).abs() < 0.01 && (right[1] - 0.8).abs() < 0.01);
+    }
+
+    #[test]
+    fn kuwahara_smooths_speckle_but_keeps_edges_sharp() {
+        let mut image = Array2::from_shape_fn((8, 8), |(_, col)| {
+            let shade = if col < 4 { 0.1_f32 } else { 0.9 };
+            Rgb::new(shade, shade, shade)
+        });
+        image[[6, 0]] = Rgb::new(0.5, 0.5, 0.5);
+
+        let smoothed = image.kuwahara(2);
+        // Every pixel has a quadrant lying wholly on its side of the edge
+        for row in 0..8 {
+            assert!((smoothed[[row, 3]].components()[0] - 0.1).abs() < 1e-5);
+            assert!((smoothed[[row, 4]].components()[0] - 0.9).abs() < 1e-5);
+        }
+        // The speckle is averaged into the flattest quadrant around it, which it occupies one ninth of
+        assert!((smoothed[[6, 0]].components()[0] - (0.1 + 0.4 / 9.0)).abs() < 1e-5);
+
+        let unchanged = image.kuwahara(0);
+        assert!(unchanged.iter().zip(&image).all(|(a, b)| a.components() == b.components()));
+    }
+
+    #[test]
+    fn map_with_neighbours_averaging_is_a_box_blur() {
+        let image = Array2::from_shape_fn((4, 5), |(row, col)| Grey::new((row * 5 + col) as f32 / 19.0));
+        let average = |window: ArrayView2<Grey<f32>>| {
+            Grey::new(window.iter().map(|pixel| pixel.components()[0]).sum::<f32>() / window.len() as f32)
+        };
+
+        for border in [BorderMode::Clamp, BorderMode::Wrap] {
+            let blurred = image.map_with_neighbours(1, border, average);
+            let expected = Array2::from_shape_fn((4, 5), |(row, col)| {
+                let mut sum = 0.0;
+                for dr in -1..=1_isize {
+                    for dc in -1..=1_isize {
+                        let (r, c) = match border {
+                            BorderMode::Clamp => ((row as isize + dr).clamp(0, 3), (col as isize + dc).clamp(0, 4)),
+                            BorderMode::Wrap => ((row as isize + dr).rem_euclid(4), (col as isize + dc).rem_euclid(5)),
+                        };
+                        sum += (r * 5 + c) as f32;
+                    }
+                }
+                sum / 19.0 / 9.0
+            });
+            assert!(
+                blurred
+                    .iter()
+                    .zip(&expected)
+                    .all(|(a, b)| (a.components()[0] - b).abs() < 1e-5),
+                "{border:?}"
+            );
+        }
+        // The corner of a clamped window repeats the corner pixel
+        let clamped = image.map_with_neighbours(1, BorderMode::Clamp, average);
+        assert!((clamped[[0, 0]].components()[0] - 18.0 / 19.0 / 9.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn bilateral_filter_handles_zero_sigmas() {
+        let mut image = Array2::from_elem((5, 5), Rgb::new(0.25_f32, 0.25, 0.5));
+        image[[2, 3]] = Rgb::new(0.75, 0.25, 0.5);
+        image[[1, 1]] = Rgb::new(0.25, 0.25, 0.52);
+
+        let unchanged = image.bilateral_filter(0.0, 0.1);
+        assert!(unchanged.iter().zip(&image).all(|(a, b)| a.components() == b.components()));
+
+        // Only exactly matching colours are averaged, so every pixel keeps its own colour
+        let exact = image.bilateral_filter(1.0, 0.0);
+        assert!(
+            exact
+                .iter()
+                .zip(&image)
+                .all(|(a, b)| { a.components().iter().zip(b.components()).all(|(x, y)| (x - y).abs() < 1e-6) })
+        );
+    }
+
+    #[test]
+    fn zero_sigma_base_and_detail_round_trip() {
+        let image = Array2::from_shape_fn((4, 4), |(row, col)| {
+            RgbAlpha::new(0.25, (row * 4 + col) as f32 / 15.0, 0.5, 0.8)
+        });
+
+        let (base, detail) = image.decompose_base_detail(0.0);
+        let recomposed = Array2::recompose_base_detail(&base, &detail);
+        for (a, b) in recomposed.iter().zip(&image) {
+            assert!(a.components().iter().zip(b.components()).all(|(a, b)| (a - b).abs() < 1e-6));
+        }
+        assert!(detail.iter().all(|pixel| pixel.components() == [0.5, 0.5, 0.5, 1.0]));
+
+        // Without a base layer to differ from, there is no detail to enhance
+        let enhanced = image.enhance_detail(0.0, 3.0);
+        assert!(enhanced.iter().zip(&image).all(|(a, b)| a.components() == b.components()));
+    }
+}
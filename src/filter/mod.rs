@@ -0,0 +1,123 @@
+use ndarray::{Array2, ArrayView2};
+use num_traits::Float;
+
+use crate::Components;
+
+mod arr2;
+
+pub(crate) use arr2::gaussian;
+
+/// How neighbourhoods extending past the image edges are sampled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BorderMode {
+    /// Sample the nearest edge pixel.
+    Clamp,
+    /// Wrap around to the opposite edge, treating the image as a torus so tileable textures remain seamless.
+    Wrap,
+}
+
+/// Trait for neighbourhood filters over an image.
+///
+/// Neighbourhoods extending past the image edges sample according to the given `BorderMode`, or the nearest edge pixel
+/// where a filter takes no border mode.
+pub trait Filter<C, T, const N: usize>
+where
+    C: Components<T, N> + Clone,
+    T: Float + Send + Sync,
+{
+    /// Blur the image with a separable Gaussian kernel extending to three standard deviations.
+    ///
+    /// Colours with an alpha channel are blurred in premultiplied form and then unpremultiplied, so fully transparent
+    /// pixels do not bleed their colour into the result.
+    fn gaussian_blur(&self, sigma: T, border: BorderMode) -> Array2<C>;
+
+    /// Approximate a Gaussian blur with three successive box blurs, at a cost per pixel independent of `sigma`.
+    ///
+    /// Box widths are chosen so the combined variance matches `sigma`, which is coarse below about two pixels. Alpha is
+    /// handled as by `gaussian_blur`.
+    fn fast_gaussian_blur(&self, sigma: T, border: BorderMode) -> Array2<C>;
+
+    /// Replace each component with its median over the `(2r + 1) x (2r + 1)` neighbourhood.
+    ///
+    /// Medians are computed on the full-precision components using a sliding window of component ranks, so the cost
+    /// grows only linearly with the radius.
+    fn median_blur(&self, radius: usize, border: BorderMode) -> Array2<C>;
+
+    /// Smooth the image with the Kuwahara filter, giving a painterly result that preserves edges.
+    ///
+    /// The `(r + 1) x (r + 1)` quadrants meeting at each pixel are compared, and the pixel takes the mean colour of the
+    /// quadrant whose luminance varies least, so all components follow the same quadrant. Edge pixels are clamped, alpha
+    /// is handled as by `gaussian_blur`, and a radius of zero returns the image unchanged.
+    fn kuwahara(&self, radius: usize) -> Array2<C>;
+
+    /// Apply a function to the `(2r + 1) x (2r + 1)` neighbourhood of each pixel, centred on the pixel.
+    ///
+    /// Neighbourhoods sample according to the border mode, so the function always sees a full window and custom local
+    /// filters need no edge handling of their own.
+    fn map_with_neighbours<F>(&self, radius: usize, border: BorderMode, f: F) -> Array2<C>
+    where
+        F: Fn(ArrayView2<C>) -> C;
+
+    /// Compute the gradient magnitude of the image's luminance using the Sobel operator.
+    fn sobel(&self) -> Array2<T>;
+
+    /// Smooth the image while preserving edges, weighting neighbours by both spatial and colour distance.
+    ///
+    /// Neighbourhoods extend to three spatial standard deviations. A zero spatial sigma returns the image unchanged, and a
+    /// zero range sigma only averages neighbours of exactly the same colour.
+    fn bilateral_filter(&self, spatial_sigma: T, range_sigma: T) -> Array2<C>;
+
+    /// Remove small-scale detail while preserving large structures by iterated joint bilateral filtering.
+    ///
+    /// The first iteration is a plain Gaussian blur, and each later iteration filters the original image guided by the
+    /// previous result, so edges of large structures are recovered while fine texture stays removed.
+    fn rolling_guidance_filter(&self, spatial_sigma: T, range_sigma: T, iterations: usize) -> Array2<C>;
+
+    /// Amplify fine detail by scaling the difference between the image and an edge-preserving base layer.
+    ///
+    /// The base layer is a bilateral filter with the given spatial sigma and a colour sigma of `0.1`, so strong edges
+    /// stay in the base and are not boosted into halos. Alpha is left unchanged.
+    fn enhance_detail(&self, sigma: T, factor: T) -> Array2<C>;
+
+    /// Sharpen the image by adding back `amount` times its difference from a Gaussian blur of the given sigma.
+    ///
+    /// Alpha is left unchanged.
+    fn unsharp_mask(&self, sigma: T, amount: T) -> Array2<C>;
+
+    /// Sharpen the image by unsharp masking, only where the Sobel gradient magnitude exceeds `edge_threshold`.
+    ///
+    /// Flat regions are left untouched, so noise in smooth areas is not amplified. Alpha is left unchanged.
+    fn sharpen_masked(&self, sigma: T, amount: T, edge_threshold: T) -> Array2<C>;
+
+    /// Split the image into an edge-preserving base layer and the residual detail layer.
+    ///
+    /// The base layer uses the same bilateral filter as `enhance_detail`. Alpha is carried entirely by the base layer,
+    /// and the detail layer is opaque. Colours cannot hold negative components, so each detail component stores
+    /// `0.5 + residual / 2`, which keeps residuals between unit-range layers within `[0, 1]`.
+    fn decompose_base_detail(&self, sigma: T) -> (Array2<C>, Array2<C>);
+
+    /// Recombine base and detail layers by adding each decoded detail residual to the base, inverting
+    /// `decompose_base_detail`.
+    fn recompose_base_detail(base: &Array2<C>, detail: &Array2<C>) -> Array2<C>;
+
+    /// Compress the dynamic range of the image using Durand and Dorsey's bilateral tone mapping operator.
+    ///
+    /// Log luminance is split into base and detail layers, and only the base layer is compressed so that its range spans
+    /// the given contrast ratio. Colours are rescaled by the change in luminance and clamped to `[0, 1]`, with the
+    /// brightest base level mapping to one. Alpha is left unchanged.
+    fn tonemap_durand(&self, contrast: T) -> Array2<C>;
+
+    /// Smooth the image with He et al.'s guided filter, following the edges of the guide image's luminance.
+    ///
+    /// Each component is filtered independently, and `epsilon` controls how strongly edges are preserved. Windows are
+    /// clipped at the image edges rather than clamped, and box sums come from integral images, so the cost does not
+    /// depend on the radius. The guide must have the same dimensions as the image.
+    fn guided_filter(&self, guide: &Array2<C>, radius: usize, epsilon: T) -> Array2<C>;
+
+    /// Segment the image into flat regions by mean-shift clustering in joint spatial and colour space.
+    ///
+    /// Each pixel climbs to the mode of the pixels within `spatial_radius` (a square window) whose colour lies within
+    /// `colour_radius`, then neighbouring pixels whose modes are closer than `colour_radius` are merged into regions
+    /// which all take their mean mode colour. Alpha is left unchanged.
+    fn mean_shift_segment(&self, spatial_radius: usize, colour_radius: T) -> Array2<C>;
+}
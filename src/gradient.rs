@@ -0,0 +1,166 @@
+//! Procedural gradient images and colour map legends.
+//!
+//! Ramps are scalar fields in `[0, 1]` which can be coloured through `Colourise`, for example with a colour map, while
+//! the gradient functions blend linearly between two colours. Legend strips sample a colour map directly.
+
+use ndarray::Array2;
+use num_traits::Float;
+use std::array;
+
+use crate::{Colourise, Components, components::cast};
+
+/// Direction along which a legend strip runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// Values rise from the left edge to the right edge.
+    Horizontal,
+    /// Values rise from the bottom edge to the top edge.
+    Vertical,
+}
+
+/// Generate a linear ramp of the given `[height, width]`, rising from zero at one corner to one at the opposite corner.
+///
+/// The ramp increases along the direction at `angle` radians from the column axis, turning towards increasing rows, so
+/// an angle of zero runs from left to right. Pixel centres lie on integer coordinates.
+pub fn linear_ramp<T: Float>(size: [usize; 2], angle: T) -> Array2<T> {
+    let [h, w] = size;
+    let (sin, cos) = angle.sin_cos();
+    let project = |row: T, col: T| col * cos + row * sin;
+
+    let (last_row, last_col) = (cast(h.saturating_sub(1)), cast(w.saturating_sub(1)));
+    let corners = [
+        project(T::zero(), T::zero()),
+        project(T::zero(), last_col),
+        project(last_row, T::zero()),
+        project(last_row, last_col),
+    ];
+    let lo = corners.iter().copied().fold(T::infinity(), T::min);
+    let hi = corners.iter().copied().fold(T::neg_infinity(), T::max);
+    let span = hi - lo;
+
+    Array2::from_shape_fn((h, w), |(row, col)| {
+        if span > T::zero() {
+            (project(cast(row), cast(col)) - lo) / span
+        } else {
+            T::zero()
+        }
+    })
+}
+
+/// Generate a radial ramp of the given `[height, width]`, rising from zero at the `[row, column]` centre to one at
+/// `radius` pixels from it, and staying at one beyond.
+pub fn radial_ramp<T: Float>(size: [usize; 2], centre: [T; 2], radius: T) -> Array2<T> {
+    assert!(radius > T::zero(), "Radius must be positive");
+
+    Array2::from_shape_fn((size[0], size[1]), |(row, col)| {
+        let (dy, dx) = (cast::<T, _>(row) - centre[0], cast::<T, _>(col) - centre[1]);
+        ((dy * dy + dx * dx).sqrt() / radius).min(T::one())
+    })
+}
+
+/// Generate an image of the given `[height, width]` blending from one colour to another along a `linear_ramp`.
+pub fn linear_gradient<C, T, const N: usize>(size: [usize; 2], from: C, to: C, angle: T) -> Array2<C>
+where
+    C: Components<T, N> + Copy,
+    T: Float + Send + Sync,
+{
+    linear_ramp(size, angle).colourise(|t| lerp(&from, &to, t))
+}
+
+/// Generate an image of the given `[height, width]` blending from the inner colour at the `[row, column]` centre to the
+/// outer colour along a `radial_ramp`.
+pub fn radial_gradient<C, T, const N: usize>(size: [usize; 2], centre: [T; 2], radius: T, inner: C, outer: C) -> Array2<C>
+where
+    C: Components<T, N> + Copy,
+    T: Float + Send + Sync,
+{
+    radial_ramp(size, centre, radius).colourise(|t| lerp(&inner, &outer, t))
+}
+
+/// Render a colour map legend of the given `[height, width]`, sampling values from zero to one along its orientation.
+///
+/// The sampler is typically a colour map lookup, such as `|x| map.sample(x)`, and the first and last pixels along the
+/// strip sample exactly zero and one. Every pixel across the strip takes the same colour.
+pub fn legend_strip<C, T, F>(size: [usize; 2], orientation: Orientation, sampler: F) -> Array2<C>
+where
+    T: Float + Send + Sync,
+    F: Fn(T) -> C,
+{
+    let [h, w] = size;
+    let position = |i: usize, len: usize| {
+        if len > 1 { cast::<T, _>(i) / cast(len - 1) } else { T::zero() }
+    };
+    let values = match orientation {
+        Orientation::Horizontal => Array2::from_shape_fn((h, w), |(_, col)| position(col, w)),
+        Orientation::Vertical => Array2::from_shape_fn((h, w), |(row, _)| position(h - 1 - row, h)),
+    };
+    values.colourise(sampler)
+}
+
+/// Helper function to blend the components of two colours by a factor in `[0, 1]`.
+fn lerp<C, T, const N: usize>(a: &C, b: &C, t: T) -> C
+where
+    C: Components<T, N>,
+    T: Float + Send + Sync,
+{
+    let (a, b) = (a.components(), b.components());
+    C::from_components(array::from_fn(|n| a[n] + (b[n] - a[n]) * t))
+}
+
+#[cfg(test)]
+mod tests {
+    use chromatic::{ColourMap, Rgb, RgbAlpha};
+    use ndarray::array;
+    use std::f32::consts::FRAC_PI_2;
+
+    use super::{Orientation, legend_strip, linear_gradient, linear_ramp, radial_gradient, radial_ramp};
+    use crate::Components;
+
+    #[test]
+    fn linear_ramps_run_along_their_angle() {
+        assert_eq!(linear_ramp([2, 3], 0.0_f32), array![[0.0, 0.5, 1.0], [0.0, 0.5, 1.0]]);
+
+        let down = linear_ramp([3, 2], FRAC_PI_2);
+        assert!(down.column(1).iter().zip([0.0, 0.5, 1.0]).all(|(&a, b)| (a - b).abs() < 1e-6));
+
+        let (red, blue) = (Rgb::new(1.0_f32, 0.0, 0.0), Rgb::new(0.0, 0.0, 1.0));
+        let gradient = linear_gradient([1, 3], red, blue, 0.0);
+        assert_eq!(gradient[[0, 0]].components(), [1.0, 0.0, 0.0]);
+        assert_eq!(gradient[[0, 1]].components(), [0.5, 0.0, 0.5]);
+        assert_eq!(gradient[[0, 2]].components(), [0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn radial_ramps_rise_to_one_at_the_radius() {
+        let ramp = radial_ramp([5, 5], [2.0_f32, 2.0], 2.0);
+        assert_eq!(ramp[[2, 2]], 0.0);
+        assert_eq!(ramp[[2, 3]], 0.5);
+        assert_eq!(ramp[[0, 2]], 1.0);
+        assert_eq!(ramp[[0, 0]], 1.0);
+
+        let (white, black) = (Rgb::new(1.0_f32, 1.0, 1.0), Rgb::new(0.0, 0.0, 0.0));
+        let gradient = radial_gradient([5, 5], [2.0, 2.0], 2.0, white, black);
+        assert_eq!(gradient[[2, 2]].components(), [1.0, 1.0, 1.0]);
+        assert_eq!(gradient[[3, 2]].components(), [0.5, 0.5, 0.5]);
+        assert_eq!(gradient[[4, 4]].components(), [0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn legend_strips_end_on_the_map_extremes() {
+        let map = ColourMap::new_uniform(&[
+            RgbAlpha::new(1.0_f32, 0.0, 0.0, 1.0),
+            RgbAlpha::new(0.0, 1.0, 0.0, 0.5),
+            RgbAlpha::new(0.0, 0.0, 1.0, 0.0),
+        ]);
+        let (first, last) = (map.sample(0.0).components(), map.sample(1.0).components());
+
+        let horizontal = legend_strip([3, 9], Orientation::Horizontal, |x| map.sample(x));
+        assert!(horizontal.column(0).iter().all(|pixel| pixel.components() == first));
+        assert!(horizontal.column(8).iter().all(|pixel| pixel.components() == last));
+
+        let vertical = legend_strip([9, 3], Orientation::Vertical, |x| map.sample(x));
+        assert!(vertical.row(8).iter().all(|pixel| pixel.components() == first));
+        assert!(vertical.row(0).iter().all(|pixel| pixel.components() == last));
+        assert_eq!(vertical[[4, 1]].components(), map.sample(0.5).components());
+    }
+}
@@ -8,7 +8,11 @@ use std::{
     path::Path,
 };
 
-use crate::{Image, PngError};
+use crate::{
+    AlphaMode, Components, Image, ImageAlpha, PngError,
+    components::{premultiply, unpremultiply},
+    downcast_u16_to_u8,
+};
 
 impl<C, T, const N: usize> Image<C, T, N> for Array2<C>
 where
@@ -26,8 +30,10 @@ where
         let (w, h) = (info.width as usize, info.height as usize);
 
         // Check bit depth
-        if info.bit_depth != BitDepth::Eight {
-            return Err(PngError::UnsupportedBitDepth(info.bit_depth));
+        let colour_type = info.color_type;
+        let bit_depth = info.bit_depth;
+        if bit_depth != BitDepth::Eight && bit_depth != BitDepth::Sixteen {
+            return Err(PngError::UnsupportedBitDepth(bit_depth));
         }
 
         // Match expected color
@@ -38,14 +44,36 @@ where
             4 => ColorType::Rgba,
             _ => return Err(PngError::InvalidChannelCount),
         };
-        if !match_colour_types(info.color_type, expected) {
-            return Err(PngError::UnsupportedColourType(info.color_type));
+        if !match_colour_types(colour_type, expected) {
+            return Err(PngError::UnsupportedColourType(colour_type));
         }
 
         // Read frame
         let mut buf = vec![0; reader.output_buffer_size()];
         reader.next_frame(&mut buf)?;
 
+        // Round 16-bit samples (stored big-endian) down to bytes
+        if bit_depth == BitDepth::Sixteen {
+            buf = buf
+                .chunks_exact(2)
+                .map(|pair| downcast_u16_to_u8(u16::from_be_bytes([pair[0], pair[1]])))
+                .collect();
+        }
+
+        // Add an opaque alpha channel, or drop the alpha channel, to match the expected layout
+        let channels = colour_type.samples();
+        if channels != N {
+            let shared = channels.min(N);
+            buf = buf
+                .chunks_exact(channels)
+                .flat_map(|sample| {
+                    let mut arr = [u8::MAX; N];
+                    arr[..shared].copy_from_slice(&sample[..shared]);
+                    arr
+                })
+                .collect();
+        }
+
         // One-liner conversion
         let pixels = buf
             .chunks_exact(N)
@@ -88,6 +116,29 @@ where
     }
 }
 
+impl<C, T, const N: usize> ImageAlpha<C, T, N> for Array2<C>
+where
+    C: Components<T, N> + Copy,
+    T: Float + Send + Sync,
+{
+    fn load_with_alpha<P: AsRef<Path>>(path: P, alpha: AlphaMode) -> Result<Self, PngError> {
+        let image = Self::load(path)?;
+        Ok(match alpha {
+            AlphaMode::Straight => image,
+            AlphaMode::Premultiplied => image.mapv(|pixel| C::from_components(premultiply(pixel.components()))),
+        })
+    }
+
+    fn save_with_alpha<P: AsRef<Path>>(&self, path: P, alpha: AlphaMode) -> Result<(), PngError> {
+        match alpha {
+            AlphaMode::Straight => self.save(path),
+            AlphaMode::Premultiplied => self
+                .mapv(|pixel| C::from_components(unpremultiply(pixel.components())))
+                .save(path),
+        }
+    }
+}
+
 /// Helper function to check if the colour types are compatible.
 fn match_colour_types(actual: ColorType, expected: ColorType) -> bool {
     // Exact match
@@ -113,3 +164,112 @@ fn match_colour_types(actual: ColorType, expected: ColorType) -> bool {
         _ => false,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chromatic::{Colour, Grey, GreyAlpha, Rgb, RgbAlpha};
+    use ndarray::{Array2, array};
+    use png::{BitDepth, ColorType, Decoder, Encoder};
+    use std::{env, fs, process};
+
+    use crate::{AlphaMode, Components, Image, ImageAlpha, PngError};
+
+    fn encode<C: Colour<f32, N> + Copy, const N: usize>(image: &Array2<C>) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        image.write(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn grey_alpha_images_round_trip_as_two_channel_pngs() {
+        let image = array![[GreyAlpha::<f32>::from_bytes([10, 20]), GreyAlpha::from_bytes([200, 255])]];
+        let bytes = encode(&image);
+        assert_eq!(
+            Decoder::new(bytes.as_slice()).read_info().unwrap().info().color_type,
+            ColorType::GrayscaleAlpha
+        );
+
+        let loaded = Array2::<GreyAlpha<f32>>::read(bytes.as_slice()).unwrap();
+        assert!(loaded.iter().zip(&image).all(|(a, b)| a.to_bytes() == b.to_bytes()));
+    }
+
+    #[test]
+    fn loading_adds_or_drops_alpha_to_match_the_layout() {
+        let grey = encode(&array![[Grey::<f32>::from_bytes([42])]]);
+        let grey_alpha = Array2::<GreyAlpha<f32>>::read(grey.as_slice()).unwrap();
+        assert_eq!(grey_alpha[[0, 0]].to_bytes(), [42, 255]);
+
+        let rgba = encode(&array![[RgbAlpha::<f32>::from_bytes([1, 2, 3, 4])]]);
+        let rgb = Array2::<Rgb<f32>>::read(rgba.as_slice()).unwrap();
+        assert_eq!(rgb[[0, 0]].to_bytes(), [1, 2, 3]);
+
+        // Colour PNGs cannot be read as grey images
+        assert!(matches!(
+            Array2::<GreyAlpha<f32>>::read(rgba.as_slice()),
+            Err(PngError::UnsupportedColourType(ColorType::Rgba))
+        ));
+    }
+
+    #[test]
+    fn load_dir_reads_sorted_pngs_and_skips_other_files() {
+        let dir = env::temp_dir().join(format!("photo-load-dir-{}", process::id()));
+        fs::create_dir_all(dir.join("nested.png")).unwrap();
+        Array2::from_elem((2, 2), RgbAlpha::<f32>::from_bytes([9, 9, 9, 255]))
+            .save(dir.join("b.png"))
+            .unwrap();
+        Array2::from_elem((1, 3), RgbAlpha::<f32>::from_bytes([1, 2, 3, 4]))
+            .save(dir.join("a.PNG"))
+            .unwrap();
+        fs::write(dir.join("notes.txt"), "not an image").unwrap();
+
+        let images = Array2::<RgbAlpha<f32>>::load_dir(&dir).unwrap();
+        let uniform = Array2::<RgbAlpha<f32>>::load_dir_uniform(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(images.iter().map(|(stem, _)| stem.as_str()).collect::<Vec<_>>(), ["a", "b"]);
+        assert_eq!(images[0].1.dim(), (1, 3));
+        assert_eq!(images[0].1[[0, 2]].to_bytes(), [1, 2, 3, 4]);
+        assert_eq!(images[1].1.dim(), (2, 2));
+        assert!(matches!(uniform, Err(PngError::DimensionMismatch)));
+    }
+
+    #[test]
+    fn premultiplied_loads_round_trip_through_straight_saves() {
+        let image = array![[
+            RgbAlpha::<f32>::from_bytes([200, 100, 50, 128]),
+            RgbAlpha::from_bytes([10, 20, 30, 255]),
+            RgbAlpha::from_bytes([90, 90, 90, 0])
+        ]];
+        let path = env::temp_dir().join(format!("photo-alpha-mode-{}.png", process::id()));
+        image.save(&path).unwrap();
+
+        let premultiplied = Array2::<RgbAlpha<f32>>::load_with_alpha(&path, AlphaMode::Premultiplied).unwrap();
+        let [red, green, _, alpha] = premultiplied[[0, 0]].components();
+        assert!((red - 200.0 / 255.0 * alpha).abs() < 1e-6 && (green - 100.0 / 255.0 * alpha).abs() < 1e-6);
+
+        premultiplied.save_with_alpha(&path, AlphaMode::Premultiplied).unwrap();
+        let restored = Array2::<RgbAlpha<f32>>::load_with_alpha(&path, AlphaMode::Straight).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        for (a, b) in restored.iter().zip(&image).take(2) {
+            assert!(a.to_bytes().iter().zip(b.to_bytes()).all(|(&a, b)| a.abs_diff(b) <= 1));
+        }
+        // Fully transparent pixels lose their colour
+        assert_eq!(restored[[0, 2]].to_bytes(), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn sixteen_bit_pngs_round_to_eight_bits() {
+        let samples: [u16; 8] = [65535, 128, 32896, 0, 257, 385, 65278, 65535];
+        let mut bytes = Vec::new();
+        let mut encoder = Encoder::new(&mut bytes, 2, 1);
+        encoder.set_color(ColorType::Rgba);
+        encoder.set_depth(BitDepth::Sixteen);
+        let data = samples.iter().flat_map(|sample| sample.to_be_bytes()).collect::<Vec<_>>();
+        encoder.write_header().unwrap().write_image_data(&data).unwrap();
+
+        let image = Array2::<RgbAlpha<f32>>::read(bytes.as_slice()).unwrap();
+        assert_eq!(image[[0, 0]].to_bytes(), [255, 0, 128, 0]);
+        assert_eq!(image[[0, 1]].to_bytes(), [1, 1, 254, 255]);
+    }
+}
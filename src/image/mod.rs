@@ -2,14 +2,26 @@ use chromatic::Colour;
 use ndarray::Array2;
 use num_traits::Float;
 use std::{
+    fs,
     io::{Read, Write},
     path::Path,
 };
 
-use crate::PngError;
+use crate::{Components, PngError};
 
 mod arr2;
 
+/// How the colour components of an image in memory relate to its alpha.
+///
+/// PNG files always store straight alpha, so images held premultiplied are converted when read and written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlphaMode {
+    /// Colour components are independent of alpha.
+    Straight,
+    /// Colour components are already multiplied by alpha.
+    Premultiplied,
+}
+
 /// Trait for image encoding/decoding operations on `Array2<C>` where `C` is a type implementing the `Colour` trait.
 pub trait Image<C, T, const N: usize>
 where
@@ -17,8 +29,49 @@ where
     T: Float + Send + Sync,
 {
     /// Read an image from a file path.
+    ///
+    /// Both 8-bit and 16-bit PNGs can be read; 16-bit samples are rounded to the nearest 8-bit value.
+    /// A PNG without alpha read into a colour type with alpha is made fully opaque, and alpha is dropped when reading a
+    /// PNG with alpha into a colour type without it. Grey and colour PNGs cannot be read as one another.
     fn load<P: AsRef<Path>>(path: P) -> Result<Array2<C>, PngError>;
 
+    /// Read every PNG in a directory, sorted by file name and paired with its file stem.
+    ///
+    /// Files without a `.png` extension and subdirectories are skipped. Images may differ in size.
+    fn load_dir<P: AsRef<Path>>(path: P) -> Result<Vec<(String, Array2<C>)>, PngError> {
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(path)? {
+            let path = entry?.path();
+            let is_png = path
+                .extension()
+                .is_some_and(|extension| extension.eq_ignore_ascii_case("png"));
+            if path.is_file() && is_png {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        paths
+            .into_iter()
+            .map(|path| {
+                let stem = path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                Ok((stem, Self::load(&path)?))
+            })
+            .collect()
+    }
+
+    /// Read every PNG in a directory as with `load_dir`, returning an error if they do not all have the same dimensions.
+    fn load_dir_uniform<P: AsRef<Path>>(path: P) -> Result<Vec<(String, Array2<C>)>, PngError> {
+        let images = Self::load_dir(path)?;
+        if images.windows(2).any(|pair| pair[0].1.dim() != pair[1].1.dim()) {
+            return Err(PngError::DimensionMismatch);
+        }
+        Ok(images)
+    }
+
     /// Read an image from a reader.
     fn read<R: Read>(reader: R) -> Result<Array2<C>, PngError>;
 
@@ -28,3 +81,21 @@ where
     /// Write an image to a writer.
     fn write<W: Write>(&self, writer: W) -> Result<(), PngError>;
 }
+
+/// Trait for reading and writing images held in a given alpha mode.
+pub trait ImageAlpha<C, T, const N: usize>: Image<C, T, N>
+where
+    C: Components<T, N> + Clone,
+    T: Float + Send + Sync,
+{
+    /// Read an image from a file path, converting it to the given alpha mode.
+    ///
+    /// Colours without an alpha channel are read unchanged.
+    fn load_with_alpha<P: AsRef<Path>>(path: P, alpha: AlphaMode) -> Result<Array2<C>, PngError>;
+
+    /// Write an image held in the given alpha mode to a file path, converting it to straight alpha.
+    ///
+    /// Fully transparent premultiplied pixels are written with zero colour, and colours without an alpha channel are
+    /// written unchanged.
+    fn save_with_alpha<P: AsRef<Path>>(&self, path: P, alpha: AlphaMode) -> Result<(), PngError>;
+}
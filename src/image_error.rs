@@ -0,0 +1,38 @@
+use std::{
+    error::Error,
+    fmt::{self, Formatter, Result as FmtResult},
+};
+
+use crate::Channel;
+
+/// Errors that can occur during image processing operations.
+#[derive(Debug)]
+pub enum ImageError {
+    EmptyPalette,
+    PaletteIndexOutOfBounds(usize),
+    EmptySequence,
+    DimensionMismatch,
+    MissingChannel(Channel),
+    TooManySeams(usize),
+    TooManyBins(usize),
+    HistogramLength(usize),
+    ComponentCount(usize),
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            ImageError::EmptyPalette => write!(f, "Palette must contain at least one colour"),
+            ImageError::PaletteIndexOutOfBounds(index) => write!(f, "Palette index out of bounds: {index}"),
+            ImageError::EmptySequence => write!(f, "Image sequence must contain at least one image"),
+            ImageError::DimensionMismatch => write!(f, "Images must all have the same dimensions"),
+            ImageError::MissingChannel(channel) => write!(f, "Colour type has no {channel:?} channel"),
+            ImageError::TooManySeams(count) => write!(f, "Cannot remove {count} seams from a narrower image"),
+            ImageError::TooManyBins(bins) => write!(f, "Too many colour bins with {bins} bins per component"),
+            ImageError::HistogramLength(len) => write!(f, "Histogram length {len} does not match the colour bin layout"),
+            ImageError::ComponentCount(len) => write!(f, "Cannot build a colour from {len} components"),
+        }
+    }
+}
+
+impl Error for ImageError {}
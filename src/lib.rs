@@ -1,9 +1,82 @@
 //! # `Photo`
 //!
 //! `Photo` is a utility library for manipulating images in Rust.
+//!
+//! Images of every colour type are `ndarray::Array2` arrays indexed `[row, column]` from the top-left pixel, and all
+//! positions and sizes taken or returned by the library follow the same order, as `[row, column]` and
+//! `[height, width]`.
 
+mod accumulator;
+mod adjust;
+mod atlas;
+mod atlas_error;
+mod background;
+mod baked_colour_map;
+mod bit_depth;
+mod blob;
+mod blob_error;
+mod colour_map_error;
+mod colour_map_ext;
+mod colourise;
+mod compare;
+mod components;
+mod composite;
+mod convert;
+mod debayer;
+mod draw;
+mod effects;
+mod filter;
+mod gradient;
 mod image;
+mod image_error;
+mod morphology;
+mod pixels;
 mod png_error;
+mod pnm;
+mod pnm_error;
+mod qoi;
+mod qoi_error;
+mod quantize;
+mod random;
+mod statistics;
+mod streaming;
+mod synthesis;
+mod tiles;
+mod transform;
 
-pub use image::Image;
+pub use accumulator::Accumulator;
+pub use adjust::{Adjust, GamutMapping};
+pub use atlas::Atlas;
+pub use atlas_error::AtlasError;
+pub use background::{background_model, foreground_mask};
+pub use baked_colour_map::BakedColourMap;
+pub use bit_depth::{downcast_u16_to_u8, upcast_u8_to_u16};
+pub use blob::Blob;
+pub use blob_error::BlobError;
+pub use colour_map_error::ColourMapError;
+pub use colour_map_ext::{ColourMapExt, InterpolationSpace, MapInterpolation};
+pub use colourise::Colourise;
+pub use compare::Compare;
+pub use components::{Components, FromColour};
+pub use composite::{BlendMode, Composite};
+pub use convert::{Channel, Convert};
+pub use debayer::{BayerPattern, debayer};
+pub use draw::Draw;
+pub use effects::Effects;
+pub use filter::{BorderMode, Filter};
+pub use gradient::{Orientation, legend_strip, linear_gradient, linear_ramp, radial_gradient, radial_ramp};
+pub use image::{AlphaMode, Image, ImageAlpha};
+pub use image_error::ImageError;
+pub use morphology::{Connectivity, close, dilate, distance_transform, erode, label_components, open};
+pub use pixels::Pixels;
 pub use png_error::PngError;
+pub use pnm::Pnm;
+pub use pnm_error::PnmError;
+pub use qoi::Qoi;
+pub use qoi_error::QoiError;
+pub use quantize::Quantize;
+pub use statistics::{Gamut, RunStats, Statistics};
+pub use streaming::process_streaming;
+pub use synthesis::Synthesis;
+pub use tiles::{Adjacency, Tiles};
+pub use transform::{Interpolation, Transform};
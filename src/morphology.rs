@@ -0,0 +1,290 @@
+//! Morphological operations on binary masks using a square structuring element, connected component labelling, and
+//! distance transforms.
+//!
+//! Pixels outside the mask are treated as off, so erosion clears pixels within `radius` of the border.
+
+use ndarray::{Array2, Axis};
+use num_traits::Float;
+
+use crate::components::cast;
+
+/// Which neighbouring pixels are considered connected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Pixels sharing an edge.
+    Four,
+    /// Pixels sharing an edge or a corner.
+    Eight,
+}
+
+/// Erode a mask, keeping only pixels whose whole `(2r + 1) x (2r + 1)` neighbourhood is on.
+pub fn erode(mask: &Array2<bool>, radius: usize) -> Array2<bool> {
+    let horizontal = sweep(mask, radius, Axis(1), |count, size| count == size);
+    sweep(&horizontal, radius, Axis(0), |count, size| count == size)
+}
+
+/// Dilate a mask, turning on every pixel with any on pixel in its `(2r + 1) x (2r + 1)` neighbourhood.
+pub fn dilate(mask: &Array2<bool>, radius: usize) -> Array2<bool> {
+    let horizontal = sweep(mask, radius, Axis(1), |count, _| count > 0);
+    sweep(&horizontal, radius, Axis(0), |count, _| count > 0)
+}
+
+/// Open a mask (erode then dilate), removing features smaller than the structuring element.
+pub fn open(mask: &Array2<bool>, radius: usize) -> Array2<bool> {
+    dilate(&erode(mask, radius), radius)
+}
+
+/// Close a mask (dilate then erode), filling gaps smaller than the structuring element.
+pub fn close(mask: &Array2<bool>, radius: usize) -> Array2<bool> {
+    erode(&dilate(mask, radius), radius)
+}
+
+/// Label the connected regions of on pixels, returning the label image and the number of regions.
+///
+/// Background pixels are labelled zero, and regions are numbered from one in row-major order of their first pixel.
+pub fn label_components(mask: &Array2<bool>, connectivity: Connectivity) -> (Array2<u32>, usize) {
+    let (h, w) = mask.dim();
+    let mut labels = Array2::<u32>::zeros((h, w));
+    let mut parents: Vec<u32> = vec![0];
+
+    // First pass: provisional labels from the already visited neighbours, recording equivalences
+    let neighbours: &[(isize, isize)] = match connectivity {
+        Connectivity::Four => &[(-1, 0), (0, -1)],
+        Connectivity::Eight => &[(-1, -1), (-1, 0), (-1, 1), (0, -1)],
+    };
+    for row in 0..h {
+        for col in 0..w {
+            if !mask[[row, col]] {
+                continue;
+            }
+            let mut label = 0;
+            for &(dr, dc) in neighbours {
+                let (Some(r), Some(c)) = (row.checked_add_signed(dr), col.checked_add_signed(dc)) else {
+                    continue;
+                };
+                let neighbour = if c < w { labels[[r, c]] } else { 0 };
+                if neighbour == 0 {
+                    continue;
+                }
+                if label == 0 {
+                    label = find(&mut parents, neighbour);
+                } else {
+                    label = union(&mut parents, label, neighbour);
+                }
+            }
+            if label == 0 {
+                label = parents.len() as u32;
+                parents.push(label);
+            }
+            labels[[row, col]] = label;
+        }
+    }
+
+    // Second pass: replace each provisional label with its region's final number
+    let mut numbers = vec![0; parents.len()];
+    let mut count = 0;
+    for label in labels.iter_mut().filter(|label| **label != 0) {
+        let root = find(&mut parents, *label) as usize;
+        if numbers[root] == 0 {
+            count += 1;
+            numbers[root] = count;
+        }
+        *label = numbers[root];
+    }
+    (labels, count as usize)
+}
+
+/// Compute the exact Euclidean distance from each pixel to the nearest on pixel of the mask.
+///
+/// On pixels have distance zero, and every pixel is infinitely far away if the mask is entirely off. Uses the separable
+/// lower-envelope algorithm of Felzenszwalb and Huttenlocher, so the cost is linear in the number of pixels.
+pub fn distance_transform<T: Float>(mask: &Array2<bool>) -> Array2<T> {
+    let mut squared = mask.map(|&on| if on { T::zero() } else { T::infinity() });
+    for axis in [Axis(1), Axis(0)] {
+        for mut lane in squared.lanes_mut(axis) {
+            let input = lane.to_vec();
+            for (out, value) in lane.iter_mut().zip(lower_envelope(&input)) {
+                *out = value;
+            }
+        }
+    }
+    squared.mapv(Float::sqrt)
+}
+
+/// Helper function to compute the one-dimensional squared distance transform of sampled values.
+///
+/// Each output is the minimum over `j` of `(i - j)^2 + values[j]`, found from the lower envelope of the parabolas
+/// rooted at the finite samples.
+fn lower_envelope<T: Float>(values: &[T]) -> Vec<T> {
+    let sites: Vec<usize> = (0..values.len()).filter(|&j| values[j].is_finite()).collect();
+    if sites.is_empty() {
+        return values.to_vec();
+    }
+
+    // Intersection of the parabolas rooted at sites `p` and `q`
+    let meet = |p: usize, q: usize| {
+        let (pf, qf) = (cast::<T, _>(p), cast::<T, _>(q));
+        ((values[q] + qf * qf) - (values[p] + pf * pf)) / ((qf - pf) * cast(2))
+    };
+    let mut hull: Vec<usize> = Vec::with_capacity(sites.len());
+    let mut starts: Vec<T> = Vec::with_capacity(sites.len());
+    for &q in &sites {
+        while let Some(&p) = hull.last() {
+            if meet(p, q) <= *starts.last().expect("Every hull site has a start") {
+                hull.pop();
+                starts.pop();
+            } else {
+                break;
+            }
+        }
+        starts.push(hull.last().map_or(T::neg_infinity(), |&p| meet(p, q)));
+        hull.push(q);
+    }
+
+    let mut k = 0;
+    (0..values.len())
+        .map(|i| {
+            let x = cast::<T, _>(i);
+            while k + 1 < hull.len() && starts[k + 1] < x {
+                k += 1;
+            }
+            let d = x - cast(hull[k]);
+            d * d + values[hull[k]]
+        })
+        .collect()
+}
+
+/// Helper function to find the root label of an equivalence set, compressing the path to it.
+fn find(parents: &mut [u32], mut label: u32) -> u32 {
+    while parents[label as usize] != label {
+        let parent = parents[label as usize];
+        parents[label as usize] = parents[parent as usize];
+        label = parent;
+    }
+    label
+}
+
+/// Helper function to merge two equivalence sets, returning the root of the merged set.
+fn union(parents: &mut [u32], a: u32, b: u32) -> u32 {
+    let (a, b) = (find(parents, a), find(parents, b));
+    let (root, child) = if a < b { (a, b) } else { (b, a) };
+    parents[child as usize] = root;
+    root
+}
+
+/// Helper function to apply a one-dimensional window test along an axis using running counts.
+///
+/// The test receives the number of on pixels in the window and the full window size.
+fn sweep<F>(mask: &Array2<bool>, radius: usize, axis: Axis, test: F) -> Array2<bool>
+where
+    F: Fn(usize, usize) -> bool,
+{
+    let size = 2 * radius + 1;
+    let mut out = Array2::from_elem(mask.dim(), false);
+    for (lane, mut out_lane) in mask.lanes(axis).into_iter().zip(out.lanes_mut(axis)) {
+        let mut prefix = Vec::with_capacity(lane.len() + 1);
+        prefix.push(0);
+        for &on in &lane {
+            prefix.push(prefix[prefix.len() - 1] + usize::from(on));
+        }
+        for (i, value) in out_lane.iter_mut().enumerate() {
+            let (start, end) = (i.saturating_sub(radius), (i + radius + 1).min(lane.len()));
+            *value = test(prefix[end] - prefix[start], size);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{Array2, s};
+
+    use super::{Connectivity, close, dilate, distance_transform, erode, label_components, open};
+
+    fn block_with_speck() -> Array2<bool> {
+        let mut mask = Array2::from_elem((7, 7), false);
+        mask.slice_mut(s![2..5, 2..5]).fill(true);
+        mask[[0, 6]] = true;
+        mask
+    }
+
+    fn count(mask: &Array2<bool>) -> usize {
+        mask.iter().filter(|&&on| on).count()
+    }
+
+    #[test]
+    fn erosion_and_dilation_use_a_square_element_with_the_border_off() {
+        let eroded = erode(&block_with_speck(), 1);
+        assert_eq!(count(&eroded), 1);
+        assert!(eroded[[3, 3]]);
+
+        let full = erode(&Array2::from_elem((5, 5), true), 1);
+        assert_eq!(count(&full), 9);
+        assert!(full.slice(s![1..4, 1..4]).iter().all(|&on| on));
+
+        let dilated = dilate(&block_with_speck(), 1);
+        assert_eq!(count(&dilated), 28);
+        assert!(dilated[[1, 5]] && !dilated[[2, 6]]);
+    }
+
+    #[test]
+    fn opening_removes_specks_and_closing_fills_holes() {
+        let mut mask = block_with_speck();
+        let opened = open(&mask, 1);
+        mask[[0, 6]] = false;
+        assert_eq!(opened, mask);
+
+        mask[[3, 3]] = false;
+        let closed = close(&mask, 1);
+        assert!(closed[[3, 3]]);
+        assert_eq!(count(&closed), 9);
+    }
+
+    #[test]
+    fn diagonally_touching_blobs_join_only_under_eight_connectivity() {
+        let mut mask = Array2::from_elem((6, 6), false);
+        mask.slice_mut(s![0..2, 0..2]).fill(true);
+        mask.slice_mut(s![2..4, 2..4]).fill(true);
+        mask[[5, 0]] = true;
+
+        let (labels, count) = label_components(&mask, Connectivity::Four);
+        assert_eq!(count, 3);
+        assert_eq!([labels[[0, 0]], labels[[3, 3]], labels[[5, 0]], labels[[0, 5]]], [1, 2, 3, 0]);
+
+        let (labels, count) = label_components(&mask, Connectivity::Eight);
+        assert_eq!(count, 2);
+        assert_eq!([labels[[0, 0]], labels[[3, 3]], labels[[5, 0]]], [1, 1, 2]);
+    }
+
+    #[test]
+    fn labelling_resolves_equivalent_labels() {
+        // A U shape whose arms get different provisional labels before meeting at the bottom
+        let mut mask = Array2::from_elem((4, 5), false);
+        mask.slice_mut(s![.., 0]).fill(true);
+        mask.slice_mut(s![.., 4]).fill(true);
+        mask.slice_mut(s![3, ..]).fill(true);
+
+        let (labels, count) = label_components(&mask, Connectivity::Four);
+        assert_eq!(count, 1);
+        assert!(labels.iter().zip(&mask).all(|(&label, &on)| label == u32::from(on)));
+    }
+
+    #[test]
+    fn distance_transform_is_exact_euclidean_distance() {
+        let mut mask = Array2::from_elem((5, 6), false);
+        mask[[1, 1]] = true;
+        mask[[4, 5]] = true;
+
+        let distances = distance_transform::<f64>(&mask);
+        for ((row, col), &distance) in distances.indexed_iter() {
+            let nearest = [[1.0, 1.0], [4.0, 5.0]]
+                .iter()
+                .map(|[r, c]| ((row as f64 - r).powi(2) + (col as f64 - c).powi(2)).sqrt())
+                .fold(f64::INFINITY, f64::min);
+            assert!((distance - nearest).abs() < 1e-12, "pixel ({row}, {col})");
+        }
+
+        let empty = distance_transform::<f32>(&Array2::from_elem((2, 2), false));
+        assert!(empty.iter().all(|distance| distance.is_infinite()));
+    }
+}
@@ -0,0 +1,71 @@
+use ndarray::Array2;
+use num_traits::Float;
+
+use crate::{Components, ImageError, Pixels};
+
+impl<C, T, const N: usize> Pixels<C, T, N> for Array2<C>
+where
+    C: Components<T, N> + Copy,
+    T: Float + Send + Sync,
+{
+    fn get_pixel_dyn(&self, [row, col]: [usize; 2]) -> Vec<T> {
+        self[[row, col]].components().to_vec()
+    }
+
+    fn set_pixel_dyn(&mut self, [row, col]: [usize; 2], components: &[T]) -> Result<(), ImageError> {
+        let components = components
+            .try_into()
+            .map_err(|_| ImageError::ComponentCount(components.len()))?;
+        self[[row, col]] = C::from_components(components);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chromatic::{Grey, GreyAlpha, Rgb, RgbAlpha};
+    use ndarray::Array2;
+
+    use crate::{Components, ImageError, Pixels};
+
+    #[test]
+    fn pixels_have_one_component_per_channel() {
+        assert_eq!(Array2::from_elem((1, 1), Grey::new(0.5_f32)).get_pixel_dyn([0, 0]).len(), 1);
+        assert_eq!(
+            Array2::from_elem((1, 1), GreyAlpha::new(0.5_f32, 1.0))
+                .get_pixel_dyn([0, 0])
+                .len(),
+            2
+        );
+        assert_eq!(
+            Array2::from_elem((1, 1), Rgb::new(0.5_f32, 0.5, 0.5))
+                .get_pixel_dyn([0, 0])
+                .len(),
+            3
+        );
+        assert_eq!(
+            Array2::from_elem((1, 1), RgbAlpha::new(0.5_f32, 0.5, 0.5, 1.0))
+                .get_pixel_dyn([0, 0])
+                .len(),
+            4
+        );
+    }
+
+    #[test]
+    fn set_pixel_round_trips() {
+        let mut image = Array2::from_elem((2, 3), RgbAlpha::new(0.0_f32, 0.0, 0.0, 1.0));
+        image.set_pixel_dyn([1, 2], &[0.25, 0.5, 0.75, 0.5]).unwrap();
+        assert_eq!(image.get_pixel_dyn([1, 2]), [0.25, 0.5, 0.75, 0.5]);
+        assert_eq!(image[[1, 2]].components(), [0.25, 0.5, 0.75, 0.5]);
+    }
+
+    #[test]
+    fn set_pixel_rejects_the_wrong_length() {
+        let mut image = Array2::from_elem((1, 1), Rgb::new(0.0_f32, 0.0, 0.0));
+        assert!(matches!(
+            image.set_pixel_dyn([0, 0], &[1.0, 1.0]),
+            Err(ImageError::ComponentCount(2))
+        ));
+        assert_eq!(image[[0, 0]].components(), [0.0, 0.0, 0.0]);
+    }
+}
@@ -0,0 +1,22 @@
+use num_traits::Float;
+
+use crate::{Components, ImageError};
+
+mod arr2;
+
+/// Trait for reading and writing pixels as component slices, so generic code need not know the channel count.
+pub trait Pixels<C, T, const N: usize>
+where
+    C: Components<T, N> + Clone,
+    T: Float + Send + Sync,
+{
+    /// Get the components of the pixel at `[row, column]`, with one entry per channel.
+    ///
+    /// Colours do not expose their storage, so the components are copied out rather than borrowed.
+    fn get_pixel_dyn(&self, coords: [usize; 2]) -> Vec<T>;
+
+    /// Set the pixel at `[row, column]` from a slice of its components.
+    ///
+    /// Returns an error if the slice length does not match the channel count.
+    fn set_pixel_dyn(&mut self, coords: [usize; 2], components: &[T]) -> Result<(), ImageError>;
+}
@@ -14,6 +14,7 @@ pub enum PngError {
     UnsupportedBitDepth(png::BitDepth),
     InvalidChannelCount,
     InvalidData,
+    DimensionMismatch,
 }
 
 impl fmt::Display for PngError {
@@ -26,6 +27,7 @@ impl fmt::Display for PngError {
             PngError::UnsupportedBitDepth(bit_depth) => write!(f, "Unsupported bit depth: {bit_depth:?}"),
             PngError::InvalidChannelCount => write!(f, "Invalid channel count for colour type"),
             PngError::InvalidData => write!(f, "Invalid data in PNG file"),
+            PngError::DimensionMismatch => write!(f, "Images must all have the same dimensions"),
         }
     }
 }
@@ -0,0 +1,190 @@
+use chromatic::Colour;
+use ndarray::Array2;
+use num_traits::Float;
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Error as IoError, ErrorKind, Read, Write},
+    path::Path,
+};
+
+use crate::{Pnm, PnmError};
+
+impl<C, T, const N: usize> Pnm<C, T, N> for Array2<C>
+where
+    C: Colour<T, N> + Copy,
+    T: Float + Send + Sync,
+{
+    fn load_pnm<P: AsRef<Path>>(path: P) -> Result<Self, PnmError> {
+        let rd = BufReader::new(File::open(path)?);
+        Self::read_pnm(rd)
+    }
+
+    fn read_pnm<R: Read>(mut reader: R) -> Result<Self, PnmError> {
+        let (magic, samples) = match N {
+            1 | 2 => ("P5", 1),
+            3 => ("P6", 3),
+            _ => return Err(PnmError::InvalidChannelCount),
+        };
+
+        // Parse header
+        let found = next_token(&mut reader)?;
+        if found != magic {
+            return Err(PnmError::UnsupportedFormat(found));
+        }
+        let w = parse_number(&mut reader)? as usize;
+        let h = parse_number(&mut reader)? as usize;
+        let max = parse_number(&mut reader)?;
+        if max == 0 || max > 255 {
+            return Err(PnmError::UnsupportedMaxValue(max));
+        }
+
+        // Read raster without trusting the header's size for the allocation
+        let len = w
+            .checked_mul(h)
+            .and_then(|pixels| pixels.checked_mul(samples))
+            .ok_or(PnmError::InvalidHeader)?;
+        let mut buf = Vec::new();
+        reader.take(len as u64).read_to_end(&mut buf)?;
+        if buf.len() != len {
+            return Err(IoError::new(ErrorKind::UnexpectedEof, "PNM raster is shorter than its header states").into());
+        }
+
+        // Rescale to the full byte range, making grey-alpha images opaque
+        let pixels = buf
+            .chunks_exact(samples)
+            .map(|chunk| {
+                let mut arr = [u8::MAX; N];
+                for (byte, &value) in arr.iter_mut().zip(chunk) {
+                    *byte = ((u32::from(value).min(max) * 255 + max / 2) / max) as u8;
+                }
+                C::from_bytes(arr)
+            })
+            .collect::<Vec<_>>();
+
+        Array2::from_shape_vec((h, w), pixels).map_err(|_| PnmError::InvalidHeader)
+    }
+
+    fn save_pnm<P: AsRef<Path>>(&self, path: P) -> Result<(), PnmError> {
+        let wr = BufWriter::new(File::create(path)?);
+        Self::write_pnm(self, wr)
+    }
+
+    fn write_pnm<W: Write>(&self, mut writer: W) -> Result<(), PnmError> {
+        let (h, w) = self.dim();
+        let (magic, samples) = match N {
+            1 | 2 => ("P5", 1),
+            3 => ("P6", 3),
+            _ => return Err(PnmError::InvalidChannelCount),
+        };
+
+        // Grey-alpha images are written as their grey channel
+        write!(writer, "{magic}\n{w} {h}\n255\n")?;
+        let mut bytes = Vec::with_capacity(w * h * samples);
+        bytes.extend(self.iter().flat_map(|px| px.to_bytes().into_iter().take(samples)));
+        writer.write_all(&bytes)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Helper function to read the next whitespace-delimited header token, skipping comments.
+///
+/// Consumes the single whitespace byte that terminates the token.
+fn next_token<R: Read>(reader: &mut R) -> Result<String, PnmError> {
+    let mut token = String::new();
+    let mut byte = [0u8];
+    loop {
+        if reader.read(&mut byte)? == 0 {
+            return Err(PnmError::InvalidHeader);
+        }
+        match byte[0] {
+            b'#' if token.is_empty() => {
+                // Skip to end of line
+                while byte[0] != b'\n' {
+                    if reader.read(&mut byte)? == 0 {
+                        return Err(PnmError::InvalidHeader);
+                    }
+                }
+            }
+            b if b.is_ascii_whitespace() => {
+                if !token.is_empty() {
+                    return Ok(token);
+                }
+            }
+            b => token.push(b as char),
+        }
+    }
+}
+
+/// Helper function to read a decimal number from the header.
+fn parse_number<R: Read>(reader: &mut R) -> Result<u32, PnmError> {
+    next_token(reader)?.parse().map_err(|_| PnmError::InvalidHeader)
+}
+
+#[cfg(test)]
+mod tests {
+    use chromatic::{Colour, Grey, GreyAlpha, Rgb};
+    use ndarray::Array2;
+    use std::io::ErrorKind;
+
+    use crate::{Pnm, PnmError};
+
+    #[test]
+    fn ppm_round_trips_through_its_binary_encoding() {
+        let image = Array2::from_shape_fn((3, 4), |(row, col)| {
+            Rgb::<f32>::from_bytes([(row * 80) as u8, (col * 60) as u8, 200])
+        });
+
+        let mut bytes = Vec::new();
+        image.write_pnm(&mut bytes).unwrap();
+        assert!(bytes.starts_with(b"P6\n4 3\n255\n"));
+        assert_eq!(bytes.len(), 11 + 3 * 4 * 3);
+
+        let loaded = Array2::<Rgb<f32>>::read_pnm(bytes.as_slice()).unwrap();
+        assert_eq!(loaded.dim(), (3, 4));
+        assert!(loaded.iter().zip(&image).all(|(a, b)| a.to_bytes() == b.to_bytes()));
+    }
+
+    #[test]
+    fn pgm_header_comments_and_max_values_are_handled() {
+        let data = b"P5\n# written by hand\n2 1\n# comment\n15\n\x00\x0f";
+        let image = Array2::<Grey<f32>>::read_pnm(&data[..]).unwrap();
+        assert_eq!(image[[0, 0]].to_bytes(), [0]);
+        assert_eq!(image[[0, 1]].to_bytes(), [255]);
+
+        assert!(matches!(
+            Array2::<Rgb<f32>>::read_pnm(&data[..]),
+            Err(PnmError::UnsupportedFormat(magic)) if magic == "P5"
+        ));
+    }
+
+    #[test]
+    fn grey_alpha_images_are_written_as_their_grey_channel() {
+        let image = Array2::from_shape_fn((2, 3), |(row, col)| {
+            GreyAlpha::<f32>::from_bytes([(row * 3 + col) as u8 * 40, 17])
+        });
+
+        let mut bytes = Vec::new();
+        image.write_pnm(&mut bytes).unwrap();
+        assert_eq!(bytes, b"P5\n3 2\n255\n\x00\x28\x50\x78\xa0\xc8");
+
+        let loaded = Array2::<GreyAlpha<f32>>::read_pnm(bytes.as_slice()).unwrap();
+        assert!(loaded.iter().zip(&image).all(|(a, b)| a.to_bytes() == [b.to_bytes()[0], 255]));
+    }
+
+    #[test]
+    fn oversized_and_truncated_rasters_are_rejected() {
+        let huge = b"P6\n4000000000 4000000000\n255\n";
+        assert!(matches!(
+            Array2::<Rgb<f32>>::read_pnm(&huge[..]),
+            Err(PnmError::InvalidHeader)
+        ));
+
+        // A plausible header with a short raster fails without allocating the full image up front
+        let short = b"P6\n40000 40000\n255\n\x01\x02\x03";
+        assert!(matches!(
+            Array2::<Rgb<f32>>::read_pnm(&short[..]),
+            Err(PnmError::IoError(err)) if err.kind() == ErrorKind::UnexpectedEof
+        ));
+    }
+}
@@ -0,0 +1,33 @@
+use chromatic::Colour;
+use ndarray::Array2;
+use num_traits::Float;
+use std::{
+    io::{Read, Write},
+    path::Path,
+};
+
+use crate::PnmError;
+
+mod arr2;
+
+/// Trait for binary PGM (`P5`) and PPM (`P6`) encoding/decoding on `Array2<C>`.
+///
+/// Single-component colours use PGM and three-component colours use PPM. Grey-alpha colours are written as a PGM of
+/// their grey channel and read back fully opaque; other channel counts are not supported.
+pub trait Pnm<C, T, const N: usize>
+where
+    C: Colour<T, N> + Clone,
+    T: Float + Send + Sync,
+{
+    /// Read a PNM image from a file path.
+    fn load_pnm<P: AsRef<Path>>(path: P) -> Result<Array2<C>, PnmError>;
+
+    /// Read a PNM image from a reader.
+    fn read_pnm<R: Read>(reader: R) -> Result<Array2<C>, PnmError>;
+
+    /// Write a PNM image to a file path.
+    fn save_pnm<P: AsRef<Path>>(&self, path: P) -> Result<(), PnmError>;
+
+    /// Write a PNM image to a writer.
+    fn write_pnm<W: Write>(&self, writer: W) -> Result<(), PnmError>;
+}
@@ -0,0 +1,35 @@
+use std::{
+    error::Error,
+    fmt::{self, Formatter, Result as FmtResult},
+    io::Error as IoError,
+};
+
+/// Errors that can occur during PNM (PGM/PPM) image operations.
+#[derive(Debug)]
+pub enum PnmError {
+    IoError(IoError),
+    UnsupportedFormat(String),
+    UnsupportedMaxValue(u32),
+    InvalidChannelCount,
+    InvalidHeader,
+}
+
+impl fmt::Display for PnmError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            PnmError::IoError(err) => write!(f, "IO error: {err}"),
+            PnmError::UnsupportedFormat(magic) => write!(f, "Unsupported PNM format: {magic}"),
+            PnmError::UnsupportedMaxValue(max) => write!(f, "Unsupported PNM maximum value: {max}"),
+            PnmError::InvalidChannelCount => write!(f, "Invalid channel count for PNM format"),
+            PnmError::InvalidHeader => write!(f, "Invalid PNM header"),
+        }
+    }
+}
+
+impl Error for PnmError {}
+
+impl From<IoError> for PnmError {
+    fn from(err: IoError) -> Self {
+        PnmError::IoError(err)
+    }
+}
@@ -0,0 +1,258 @@
+use chromatic::Colour;
+use ndarray::Array2;
+use num_traits::Float;
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use crate::{Qoi, QoiError};
+
+const MAGIC: &[u8; 4] = b"qoif";
+const END_MARKER: [u8; 8] = [0, 0, 0, 0, 0, 0, 0, 1];
+
+const OP_INDEX: u8 = 0x00;
+const OP_DIFF: u8 = 0x40;
+const OP_LUMA: u8 = 0x80;
+const OP_RUN: u8 = 0xc0;
+const OP_RGB: u8 = 0xfe;
+const OP_RGBA: u8 = 0xff;
+const MASK: u8 = 0xc0;
+
+impl<C, T, const N: usize> Qoi<C, T, N> for Array2<C>
+where
+    C: Colour<T, N> + Copy,
+    T: Float + Send + Sync,
+{
+    fn load_qoi<P: AsRef<Path>>(path: P) -> Result<Self, QoiError> {
+        let rd = BufReader::new(File::open(path)?);
+        Self::read_qoi(rd)
+    }
+
+    fn read_qoi<R: Read>(mut reader: R) -> Result<Self, QoiError> {
+        if N != 3 && N != 4 {
+            return Err(QoiError::InvalidChannelCount);
+        }
+
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        // Parse header
+        if data.len() < 14 + END_MARKER.len() || &data[..4] != MAGIC {
+            return Err(QoiError::InvalidHeader);
+        }
+        let w = u32::from_be_bytes([data[4], data[5], data[6], data[7]]) as usize;
+        let h = u32::from_be_bytes([data[8], data[9], data[10], data[11]]) as usize;
+        if !matches!(data[12], 3 | 4) || data[13] > 1 {
+            return Err(QoiError::InvalidHeader);
+        }
+
+        // Each chunk byte decodes to at most 62 pixels, so reject sizes the data cannot hold before allocating
+        let len = w.checked_mul(h).ok_or(QoiError::InvalidData)?;
+        if len > (data.len() - 14 - END_MARKER.len()).saturating_mul(62) {
+            return Err(QoiError::InvalidData);
+        }
+
+        // Decode chunks
+        let mut bytes = data[14..].iter().copied();
+        let mut next = || bytes.next().ok_or(QoiError::InvalidData);
+        let mut index = [[0u8; 4]; 64];
+        let mut px = [0, 0, 0, 255];
+        let mut run = 0;
+        let mut pixels = Vec::with_capacity(len);
+        for _ in 0..len {
+            if run > 0 {
+                run -= 1;
+            } else {
+                let b1 = next()?;
+                match b1 {
+                    OP_RGB => px = [next()?, next()?, next()?, px[3]],
+                    OP_RGBA => px = [next()?, next()?, next()?, next()?],
+                    _ => match b1 & MASK {
+                        OP_INDEX => px = index[b1 as usize],
+                        OP_DIFF => {
+                            px[0] = px[0].wrapping_add(((b1 >> 4) & 0x03).wrapping_sub(2));
+                            px[1] = px[1].wrapping_add(((b1 >> 2) & 0x03).wrapping_sub(2));
+                            px[2] = px[2].wrapping_add((b1 & 0x03).wrapping_sub(2));
+                        }
+                        OP_LUMA => {
+                            let b2 = next()?;
+                            let vg = (b1 & 0x3f).wrapping_sub(32);
+                            px[0] = px[0].wrapping_add(vg.wrapping_sub(8).wrapping_add((b2 >> 4) & 0x0f));
+                            px[1] = px[1].wrapping_add(vg);
+                            px[2] = px[2].wrapping_add(vg.wrapping_sub(8).wrapping_add(b2 & 0x0f));
+                        }
+                        _ => run = b1 & 0x3f,
+                    },
+                }
+                index[hash(px)] = px;
+            }
+
+            let mut arr = [0u8; N];
+            arr.copy_from_slice(&px[..N]);
+            pixels.push(C::from_bytes(arr));
+        }
+
+        Array2::from_shape_vec((h, w), pixels).map_err(|_| QoiError::InvalidData)
+    }
+
+    fn save_qoi<P: AsRef<Path>>(&self, path: P) -> Result<(), QoiError> {
+        let wr = BufWriter::new(File::create(path)?);
+        Self::write_qoi(self, wr)
+    }
+
+    fn write_qoi<W: Write>(&self, mut writer: W) -> Result<(), QoiError> {
+        if N != 3 && N != 4 {
+            return Err(QoiError::InvalidChannelCount);
+        }
+        let (h, w) = self.dim();
+
+        // Header
+        let mut out = Vec::with_capacity(14 + w * h * (N + 1) + END_MARKER.len());
+        out.extend_from_slice(MAGIC);
+        out.extend_from_slice(&(w as u32).to_be_bytes());
+        out.extend_from_slice(&(h as u32).to_be_bytes());
+        out.extend_from_slice(&[N as u8, 0]);
+
+        // Encode pixels in row-major order
+        let mut index = [[0u8; 4]; 64];
+        let mut prev = [0, 0, 0, 255];
+        let mut run = 0;
+        let total = w * h;
+        for (i, colour) in self.iter().enumerate() {
+            let mut px = [0, 0, 0, 255];
+            px[..N].copy_from_slice(&colour.to_bytes());
+
+            if px == prev {
+                run += 1;
+                if run == 62 || i + 1 == total {
+                    out.push(OP_RUN | (run - 1));
+                    run = 0;
+                }
+                continue;
+            }
+            if run > 0 {
+                out.push(OP_RUN | (run - 1));
+                run = 0;
+            }
+
+            let hash = hash(px);
+            if index[hash] == px {
+                out.push(OP_INDEX | hash as u8);
+            } else {
+                index[hash] = px;
+                if px[3] == prev[3] {
+                    let vr = px[0].wrapping_sub(prev[0]) as i8;
+                    let vg = px[1].wrapping_sub(prev[1]) as i8;
+                    let vb = px[2].wrapping_sub(prev[2]) as i8;
+                    let (vg_r, vg_b) = (vr.wrapping_sub(vg), vb.wrapping_sub(vg));
+                    if (-2..2).contains(&vr) && (-2..2).contains(&vg) && (-2..2).contains(&vb) {
+                        out.push(OP_DIFF | ((vr + 2) as u8) << 4 | ((vg + 2) as u8) << 2 | (vb + 2) as u8);
+                    } else if (-8..8).contains(&vg_r) && (-32..32).contains(&vg) && (-8..8).contains(&vg_b) {
+                        out.push(OP_LUMA | (vg + 32) as u8);
+                        out.push(((vg_r + 8) as u8) << 4 | (vg_b + 8) as u8);
+                    } else {
+                        out.extend_from_slice(&[OP_RGB, px[0], px[1], px[2]]);
+                    }
+                } else {
+                    out.extend_from_slice(&[OP_RGBA, px[0], px[1], px[2], px[3]]);
+                }
+            }
+            prev = px;
+        }
+        out.extend_from_slice(&END_MARKER);
+
+        writer.write_all(&out)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Helper function to compute the index position of a pixel in the running colour array.
+fn hash(px: [u8; 4]) -> usize {
+    let [r, g, b, a] = px.map(usize::from);
+    (r * 3 + g * 5 + b * 7 + a * 11) % 64
+}
+
+#[cfg(test)]
+mod tests {
+    use chromatic::{Colour, RgbAlpha};
+    use ndarray::Array2;
+
+    use super::OP_RUN;
+    use crate::{Qoi, QoiError, random::SplitMix64};
+
+    #[test]
+    fn noisy_rgba_image_round_trips_losslessly() {
+        let mut rng = SplitMix64::new(7);
+        let image = Array2::from_shape_fn((17, 23), |(row, col)| {
+            // Mix flat runs, gentle gradients and noise so every chunk type is exercised
+            let step = (col * 3) as u8;
+            let bytes = if row % 4 == 0 {
+                [10, 20, 30, 255]
+            } else if row % 4 == 1 {
+                [step, step + 1, step.saturating_sub(col as u8 / 2), 255]
+            } else {
+                let value = rng.next_u64().to_le_bytes();
+                [value[0], value[1], value[2], if row % 2 == 0 { 255 } else { value[3] }]
+            };
+            RgbAlpha::<f32>::from_bytes(bytes)
+        });
+
+        let mut bytes = Vec::new();
+        image.write_qoi(&mut bytes).unwrap();
+        assert_eq!(&bytes[..4], b"qoif");
+        assert_eq!(&bytes[bytes.len() - 8..], &[0, 0, 0, 0, 0, 0, 0, 1]);
+
+        let loaded = Array2::<RgbAlpha<f32>>::read_qoi(bytes.as_slice()).unwrap();
+        assert_eq!(loaded.dim(), (17, 23));
+        assert!(loaded.iter().zip(&image).all(|(a, b)| a.to_bytes() == b.to_bytes()));
+    }
+
+    #[test]
+    fn truncated_bodies_and_oversized_headers_are_invalid_data() {
+        let image = Array2::from_shape_fn((8, 8), |(row, col)| {
+            RgbAlpha::<f32>::from_bytes([row as u8 * 31, col as u8 * 29, 7, 255])
+        });
+        let mut bytes = Vec::new();
+        image.write_qoi(&mut bytes).unwrap();
+
+        let truncated = &bytes[..bytes.len() / 2];
+        assert!(matches!(
+            Array2::<RgbAlpha<f32>>::read_qoi(truncated),
+            Err(QoiError::InvalidData)
+        ));
+
+        // A tiny file claiming 50000 x 50000 pixels is rejected before anything is allocated
+        let mut huge = b"qoif".to_vec();
+        huge.extend_from_slice(&50_000_u32.to_be_bytes());
+        huge.extend_from_slice(&50_000_u32.to_be_bytes());
+        huge.extend_from_slice(&[4, 0, OP_RUN | 61, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+        assert!(matches!(
+            Array2::<RgbAlpha<f32>>::read_qoi(huge.as_slice()),
+            Err(QoiError::InvalidData)
+        ));
+    }
+
+    #[test]
+    fn malformed_headers_are_rejected() {
+        let mut bytes = Vec::new();
+        Array2::from_elem((2, 2), RgbAlpha::<f32>::from_bytes([1, 2, 3, 4]))
+            .write_qoi(&mut bytes)
+            .unwrap();
+
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] = b'x';
+        let mut bad_channels = bytes.clone();
+        bad_channels[12] = 5;
+        let mut bad_colour_space = bytes.clone();
+        bad_colour_space[13] = 2;
+        for bad in [bad_magic, bad_channels, bad_colour_space, bytes[..10].to_vec()] {
+            assert!(matches!(
+                Array2::<RgbAlpha<f32>>::read_qoi(bad.as_slice()),
+                Err(QoiError::InvalidHeader)
+            ));
+        }
+    }
+}
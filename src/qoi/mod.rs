@@ -0,0 +1,33 @@
+use chromatic::Colour;
+use ndarray::Array2;
+use num_traits::Float;
+use std::{
+    io::{Read, Write},
+    path::Path,
+};
+
+use crate::QoiError;
+
+mod arr2;
+
+/// Trait for [QOI](https://qoiformat.org) ("Quite OK Image") encoding/decoding on `Array2<C>`.
+///
+/// Three-component colours are stored as RGB and four-component colours as RGBA; other channel counts are not
+/// supported.
+pub trait Qoi<C, T, const N: usize>
+where
+    C: Colour<T, N> + Clone,
+    T: Float + Send + Sync,
+{
+    /// Read a QOI image from a file path.
+    fn load_qoi<P: AsRef<Path>>(path: P) -> Result<Array2<C>, QoiError>;
+
+    /// Read a QOI image from a reader.
+    fn read_qoi<R: Read>(reader: R) -> Result<Array2<C>, QoiError>;
+
+    /// Write a QOI image to a file path.
+    fn save_qoi<P: AsRef<Path>>(&self, path: P) -> Result<(), QoiError>;
+
+    /// Write a QOI image to a writer.
+    fn write_qoi<W: Write>(&self, writer: W) -> Result<(), QoiError>;
+}
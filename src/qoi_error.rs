@@ -0,0 +1,33 @@
+use std::{
+    error::Error,
+    fmt::{self, Formatter, Result as FmtResult},
+    io::Error as IoError,
+};
+
+/// Errors that can occur during QOI image operations.
+#[derive(Debug)]
+pub enum QoiError {
+    IoError(IoError),
+    InvalidHeader,
+    InvalidChannelCount,
+    InvalidData,
+}
+
+impl fmt::Display for QoiError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            QoiError::IoError(err) => write!(f, "IO error: {err}"),
+            QoiError::InvalidHeader => write!(f, "Invalid QOI header"),
+            QoiError::InvalidChannelCount => write!(f, "Invalid channel count for QOI format"),
+            QoiError::InvalidData => write!(f, "Invalid data in QOI file"),
+        }
+    }
+}
+
+impl Error for QoiError {}
+
+impl From<IoError> for QoiError {
+    fn from(err: IoError) -> Self {
+        QoiError::IoError(err)
+    }
+}
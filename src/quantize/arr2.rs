@@ -0,0 +1,246 @@
+use ndarray::Array2;
+use num_traits::Float;
+use std::cmp::Ordering;
+
+use crate::{
+    Components, ImageError, Quantize,
+    components::{cast, has_alpha, nearest},
+};
+
+impl<C, T, const N: usize> Quantize<C, T, N> for Array2<C>
+where
+    C: Components<T, N> + Copy,
+    T: Float + Send + Sync,
+{
+    fn quantize(&self, palette: &[C]) -> Result<Array2<usize>, ImageError> {
+        if palette.is_empty() {
+            return Err(ImageError::EmptyPalette);
+        }
+
+        let palette = palette.iter().map(Components::components).collect::<Vec<_>>();
+        Ok(self.map(|pixel| nearest(&palette, &pixel.components())))
+    }
+
+    fn quantize_adaptive(&self, colours: usize) -> (Vec<C>, Array2<usize>) {
+        assert!(colours > 0, "Palette must have at least one colour");
+        if self.is_empty() {
+            return (Vec::new(), Array2::zeros(self.dim()));
+        }
+
+        let mut boxes = vec![self.iter().map(Components::components).collect::<Vec<_>>()];
+        while boxes.len() < colours {
+            // Split the box with the widest range, stopping once every box holds a single colour
+            let Some((index, axis, _)) = boxes
+                .iter()
+                .enumerate()
+                .map(|(index, pixels)| {
+                    let (axis, range) = widest_axis(pixels);
+                    (index, axis, range)
+                })
+                .filter(|&(_, _, range)| range > T::zero())
+                .max_by(|a, b| a.2.partial_cmp(&b.2).unwrap_or(Ordering::Equal))
+            else {
+                break;
+            };
+
+            let mut pixels = boxes.swap_remove(index);
+            pixels.sort_by(|a, b| a[axis].partial_cmp(&b[axis]).unwrap_or(Ordering::Equal));
+            let middle = pixels.len() / 2;
+            let cut = (1..pixels.len())
+                .filter(|&i| pixels[i - 1][axis] != pixels[i][axis])
+                .min_by_key(|&i| i.abs_diff(middle))
+                .expect("A box with a non-zero range has differing values");
+            let upper = pixels.split_off(cut);
+            boxes.push(pixels);
+            boxes.push(upper);
+        }
+
+        let palette = boxes
+            .iter()
+            .map(|pixels| {
+                let count: T = cast(pixels.len());
+                let sum = pixels.iter().fold([T::zero(); N], |mut sum, pixel| {
+                    for (s, &v) in sum.iter_mut().zip(pixel) {
+                        *s = *s + v;
+                    }
+                    sum
+                });
+                C::from_components(sum.map(|s| s / count))
+            })
+            .collect::<Vec<_>>();
+        let indices = self.quantize(&palette).expect("Palette is not empty");
+        (palette, indices)
+    }
+
+    fn dither_floyd_steinberg(&self, palette: &[C]) -> Result<Array2<usize>, ImageError> {
+        if palette.is_empty() {
+            return Err(ImageError::EmptyPalette);
+        }
+
+        let palette = palette.iter().map(Components::components).collect::<Vec<_>>();
+        let weights = [7.0, 3.0, 5.0, 1.0].map(|w: f64| cast::<T, _>(w / 16.0));
+        let (h, w) = self.dim();
+        let mut buffer = self.map(Components::components);
+        let mut indices = Array2::zeros((h, w));
+        for row in 0..h {
+            for col in 0..w {
+                let old = buffer[[row, col]];
+                let index = nearest(&palette, &old);
+                indices[[row, col]] = index;
+
+                // Diffuse the quantization error to unvisited neighbours, dropping shares that fall off the edge
+                let neighbours = [
+                    (row, col + 1),
+                    (row + 1, col.wrapping_sub(1)),
+                    (row + 1, col),
+                    (row + 1, col + 1),
+                ];
+                for ((r, c), weight) in neighbours.into_iter().zip(weights) {
+                    if let Some(target) = buffer.get_mut([r, c]) {
+                        for n in 0..N {
+                            target[n] = target[n] + (old[n] - palette[index][n]) * weight;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(indices)
+    }
+
+    fn apply_palette(indices: &Array2<usize>, palette: &[C]) -> Result<Self, ImageError> {
+        if palette.is_empty() {
+            return Err(ImageError::EmptyPalette);
+        }
+        if let Some(&index) = indices.iter().find(|&&index| index >= palette.len()) {
+            return Err(ImageError::PaletteIndexOutOfBounds(index));
+        }
+
+        Ok(indices.map(|&index| palette[index]))
+    }
+
+    fn apply_palette_transparent(indices: &Array2<usize>, palette: &[C], transparent: usize) -> Result<Self, ImageError> {
+        assert!(has_alpha(N), "Colour type must have an alpha channel");
+        if transparent >= palette.len() {
+            return Err(ImageError::PaletteIndexOutOfBounds(transparent));
+        }
+
+        let expanded = palette
+            .iter()
+            .enumerate()
+            .map(|(index, colour)| {
+                if index == transparent {
+                    return C::from_components([T::zero(); N]);
+                }
+                let mut components = colour.components();
+                components[N - 1] = T::one();
+                C::from_components(components)
+            })
+            .collect::<Vec<_>>();
+        Self::apply_palette(indices, &expanded)
+    }
+}
+
+/// Helper function to find the component with the widest range of values over a box of pixels, and that range.
+fn widest_axis<T: Float, const N: usize>(pixels: &[[T; N]]) -> (usize, T) {
+    (0..N)
+        .map(|n| {
+            let (lo, hi) = pixels.iter().fold((T::infinity(), T::neg_infinity()), |(lo, hi), pixel| {
+                (lo.min(pixel[n]), hi.max(pixel[n]))
+            });
+            (n, hi - lo)
+        })
+        .fold(
+            (0, T::zero()),
+            |best, candidate| if candidate.1 > best.1 { candidate } else { best },
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use chromatic::{Grey, Rgb, RgbAlpha};
+    use ndarray::{Array2, array};
+
+    use crate::{Components, ImageError, Quantize};
+
+    #[test]
+    fn equal_colours_with_different_alpha_get_distinct_entries() {
+        let opaque = RgbAlpha::new(0.8_f32, 0.2, 0.4, 1.0);
+        let translucent = RgbAlpha::new(0.8, 0.2, 0.4, 0.25);
+        let image = Array2::from_shape_fn((4, 4), |(row, _)| if row < 2 { opaque } else { translucent });
+
+        let (palette, indices) = image.quantize_adaptive(4);
+        assert_eq!(palette.len(), 2);
+        assert_ne!(indices[[0, 0]], indices[[3, 0]]);
+        let close =
+            |a: RgbAlpha<f32>, b: RgbAlpha<f32>| a.components().iter().zip(b.components()).all(|(a, b)| (a - b).abs() < 1e-6);
+        assert!(close(palette[indices[[0, 0]]], opaque));
+        assert!(close(palette[indices[[3, 0]]], translucent));
+    }
+
+    #[test]
+    fn quantize_adaptive_limits_the_palette_size() {
+        let image = Array2::from_shape_fn((8, 8), |(row, col)| {
+            RgbAlpha::new(row as f32 / 7.0, col as f32 / 7.0, 0.5, 1.0)
+        });
+
+        let (palette, indices) = image.quantize_adaptive(5);
+        assert_eq!(palette.len(), 5);
+        assert!(indices.iter().all(|&index| index < 5));
+    }
+
+    #[test]
+    fn quantize_picks_the_nearest_palette_entry_and_round_trips() {
+        let palette = [Rgb::new(0.0_f32, 0.0, 0.0), Rgb::new(1.0, 0.0, 0.0), Rgb::new(0.0, 0.0, 1.0)];
+        let image = array![
+            [Rgb::new(0.1, 0.0, 0.1), Rgb::new(0.9, 0.2, 0.0)],
+            [Rgb::new(0.2, 0.1, 0.7), palette[1]]
+        ];
+
+        let indices = image.quantize(&palette).unwrap();
+        assert_eq!(indices, array![[0, 1], [2, 1]]);
+
+        let rebuilt = Array2::apply_palette(&indices, &palette).unwrap();
+        assert_eq!(rebuilt[[1, 0]].components(), palette[2].components());
+        assert!(matches!(image.quantize(&[]), Err(ImageError::EmptyPalette)));
+        assert!(matches!(
+            Array2::apply_palette(&array![[3]], &palette),
+            Err(ImageError::PaletteIndexOutOfBounds(3))
+        ));
+    }
+
+    #[test]
+    fn dithered_gradient_averages_close_to_the_original() {
+        let palette = [Grey::new(0.0_f32), Grey::new(1.0)];
+        let image = Array2::from_shape_fn((16, 32), |(_, col)| Grey::new(col as f32 / 31.0));
+
+        let indices = image.dither_floyd_steinberg(&palette).unwrap();
+        let dithered = Array2::apply_palette(&indices, &palette).unwrap();
+        for col in [4, 16, 28] {
+            let original = image.column(col).iter().map(|pixel| pixel.components()[0]).sum::<f32>() / 16.0;
+            let mean = dithered.column(col).iter().map(|pixel| pixel.components()[0]).sum::<f32>() / 16.0;
+            assert!((original - mean).abs() < 0.2, "column {col}: {original} vs {mean}");
+        }
+        let total = |image: &Array2<Grey<f32>>| image.iter().map(|pixel| pixel.components()[0]).sum::<f32>();
+        assert!((total(&image) - total(&dithered)).abs() / 512.0 < 0.01);
+    }
+
+    #[test]
+    fn transparent_index_expands_to_zero_alpha() {
+        let palette = [
+            RgbAlpha::new(0.0_f32, 0.0, 0.0, 1.0),
+            RgbAlpha::new(1.0, 0.5, 0.0, 1.0),
+            RgbAlpha::new(0.0, 0.5, 1.0, 0.3),
+        ];
+        let indices = array![[0, 1], [2, 1]];
+
+        let expanded = Array2::apply_palette_transparent(&indices, &palette, 1).unwrap();
+        assert_eq!(expanded.map(|pixel| pixel.components()[3]), array![[1.0, 0.0], [1.0, 0.0]]);
+        assert_eq!(expanded[[1, 0]].components(), [0.0, 0.5, 1.0, 1.0]);
+
+        assert!(matches!(
+            Array2::apply_palette_transparent(&indices, &palette, 3),
+            Err(ImageError::PaletteIndexOutOfBounds(3))
+        ));
+    }
+}
@@ -0,0 +1,38 @@
+use ndarray::Array2;
+use num_traits::Float;
+
+use crate::{Components, ImageError};
+
+mod arr2;
+
+/// Trait for reducing an image to a fixed palette of colours.
+///
+/// Colour distances are Euclidean over the colour components.
+pub trait Quantize<C, T, const N: usize>
+where
+    C: Components<T, N> + Clone,
+    T: Float + Send + Sync,
+{
+    /// Map each pixel to the index of the nearest palette colour.
+    fn quantize(&self, palette: &[C]) -> Result<Array2<usize>, ImageError>;
+
+    /// Generate a palette of up to `colours` colours for the image by median cut, and map each pixel to its nearest entry.
+    ///
+    /// Every component, including alpha, is clustered alike, so distinct levels of transparency keep distinct palette
+    /// entries. The box of pixels with the widest range in any component is repeatedly split between differing values
+    /// nearest its median, and each palette colour is the mean of a box. Images with fewer distinct colours than
+    /// requested give a smaller palette, and an empty image gives an empty palette.
+    fn quantize_adaptive(&self, colours: usize) -> (Vec<C>, Array2<usize>);
+
+    /// Map each pixel to a palette index using Floyd-Steinberg error diffusion.
+    fn dither_floyd_steinberg(&self, palette: &[C]) -> Result<Array2<usize>, ImageError>;
+
+    /// Reconstruct an image from palette indices.
+    fn apply_palette(indices: &Array2<usize>, palette: &[C]) -> Result<Array2<C>, ImageError>;
+
+    /// Reconstruct an image from palette indices with GIF-style transparency.
+    ///
+    /// Pixels with the `transparent` index become fully transparent and all others take their palette colour made
+    /// fully opaque. The colour type must have an alpha channel.
+    fn apply_palette_transparent(indices: &Array2<usize>, palette: &[C], transparent: usize) -> Result<Array2<C>, ImageError>;
+}
@@ -0,0 +1,395 @@
+use chromatic::Convert;
+use ndarray::{Array2, Zip};
+use num_traits::Float;
+use std::{array, cmp::Ordering};
+
+use crate::{
+    BorderMode, Components, Draw, Gamut, ImageError, RunStats, Statistics,
+    components::{cast, colour_count, distance_squared, has_alpha},
+    filter::gaussian,
+};
+
+/// Standard deviation of the blur removing fine texture and noise before measuring saliency.
+const SALIENCY_SIGMA: f64 = 1.0;
+
+impl<C, T, const N: usize> Statistics<C, T, N> for Array2<C>
+where
+    C: Components<T, N> + Copy,
+    T: Float + Send + Sync,
+{
+    fn colour_coherence_vector(&self, bins: usize, tau: usize) -> Result<Vec<(u32, u32)>, ImageError> {
+        assert!((1..=256).contains(&bins), "Bin count must be between 1 and 256");
+        let bin_count = colour_bin_count(bins, N)?;
+
+        let (h, w) = self.dim();
+        let labels = self.map(|pixel| colour_bin(pixel, bins));
+
+        // Flood fill each region of equal bins, classifying its pixels by region size
+        let mut ccv = vec![(0, 0); bin_count];
+        let mut visited = Array2::from_elem((h, w), false);
+        let mut stack = Vec::new();
+        for ((row, col), &bin) in labels.indexed_iter() {
+            if visited[[row, col]] {
+                continue;
+            }
+            visited[[row, col]] = true;
+            stack.push((row, col));
+            let mut size = 0u32;
+            while let Some((r, c)) = stack.pop() {
+                size += 1;
+                for nr in r.saturating_sub(1)..(r + 2).min(h) {
+                    for nc in c.saturating_sub(1)..(c + 2).min(w) {
+                        if !visited[[nr, nc]] && labels[[nr, nc]] == bin {
+                            visited[[nr, nc]] = true;
+                            stack.push((nr, nc));
+                        }
+                    }
+                }
+            }
+
+            if size as usize > tau {
+                ccv[bin].0 += size;
+            } else {
+                ccv[bin].1 += size;
+            }
+        }
+
+        Ok(ccv)
+    }
+
+    fn colour_histogram(&self, bins: usize) -> Result<Vec<u32>, ImageError> {
+        assert!((1..=256).contains(&bins), "Bin count must be between 1 and 256");
+
+        let mut histogram = vec![0; colour_bin_count(bins, N)?];
+        for pixel in self {
+            histogram[colour_bin(pixel, bins)] += 1;
+        }
+        Ok(histogram)
+    }
+
+    fn channel_histograms(&self, bins: usize) -> [Vec<u32>; N] {
+        assert!((1..=256).contains(&bins), "Bin count must be between 1 and 256");
+
+        let mut histograms = array::from_fn(|_| vec![0; bins]);
+        for pixel in self {
+            for (histogram, value) in histograms.iter_mut().zip(pixel.to_bytes()) {
+                histogram[value as usize * bins / 256] += 1;
+            }
+        }
+        histograms
+    }
+
+    fn histogram_plot<D>(&self, size: [usize; 2]) -> Array2<D>
+    where
+        D: Components<T, 3> + Copy,
+    {
+        let [h, w] = size;
+        let black = D::from_components([T::zero(); 3]);
+        let mut plot = Array2::from_elem((h, w), black);
+        if h == 0 || w == 0 {
+            return plot;
+        }
+
+        let colours = colour_count(N);
+        let histograms = self.channel_histograms(256);
+        let peak = histograms[..colours].iter().flatten().copied().max().unwrap_or(0).max(1);
+        for (channel, histogram) in histograms[..colours].iter().enumerate() {
+            // Draw each curve on its own canvas, then add it to the plot
+            let colour = D::from_components(array::from_fn(|n| {
+                if colours == 1 || n == channel { T::one() } else { T::zero() }
+            }));
+            let points = histogram
+                .iter()
+                .enumerate()
+                .map(|(bin, &count)| {
+                    let row = (h - 1) - (count as usize * (h - 1)).div_ceil(peak as usize);
+                    [row as i32, (bin * (w - 1) / 255) as i32]
+                })
+                .collect::<Vec<_>>();
+            let mut curve = Array2::from_elem((h, w), black);
+            curve.draw_polyline(&points, 1, colour);
+            Zip::from(&mut plot).and(&curve).for_each(|total, stroke| {
+                let (a, b) = (total.components(), stroke.components());
+                *total = D::from_components(array::from_fn(|n| (a[n] + b[n]).min(T::one())));
+            });
+        }
+        plot
+    }
+
+    fn run_length_stats(&self) -> RunStats {
+        let mut stats = RunStats {
+            runs: 0,
+            pixels: self.len(),
+            longest: 0,
+        };
+        for row in self.rows() {
+            let mut length = 0;
+            let mut previous = None;
+            for pixel in row {
+                let components = pixel.components();
+                if previous != Some(components) {
+                    stats.runs += 1;
+                    length = 0;
+                    previous = Some(components);
+                }
+                length += 1;
+                stats.longest = stats.longest.max(length);
+            }
+        }
+        stats
+    }
+
+    fn backproject(&self, model: &[u32], bins: usize) -> Result<Array2<T>, ImageError> {
+        assert!((1..=256).contains(&bins), "Bin count must be between 1 and 256");
+        if colour_bin_count(bins, N).ok() != Some(model.len()) {
+            return Err(ImageError::HistogramLength(model.len()));
+        }
+
+        let peak = model.iter().copied().max().unwrap_or(0);
+        if peak == 0 {
+            return Ok(Array2::zeros(self.dim()));
+        }
+        let scale = cast::<T, _>(peak).recip();
+        Ok(self.map(|pixel| cast::<T, _>(model[colour_bin(pixel, bins)]) * scale))
+    }
+
+    fn edge_seam_error(&self) -> T {
+        let (h, w) = self.dim();
+        if h == 0 || w == 0 {
+            return T::zero();
+        }
+
+        let pairs = (0..h)
+            .map(|row| (self[[row, w - 1]], self[[row, 0]]))
+            .chain((0..w).map(|col| (self[[h - 1, col]], self[[0, col]])));
+        let total = pairs.fold(T::zero(), |acc, (a, b)| {
+            acc + distance_squared(&a.components(), &b.components())
+        });
+        total / cast((h + w) * N)
+    }
+
+    fn saliency(&self) -> Array2<T> {
+        if self.is_empty() {
+            return Array2::zeros(self.dim());
+        }
+
+        let colours = colour_count(N);
+        let components = self.map(|pixel| pixel.components());
+        let mut mean = [T::zero(); N];
+        for pixel in &components {
+            for (m, &v) in mean.iter_mut().zip(pixel) {
+                *m = *m + v;
+            }
+        }
+        let mean = mean.map(|m| m / cast(self.len()));
+
+        gaussian(&components, cast(SALIENCY_SIGMA), BorderMode::Clamp).map(|blurred| {
+            blurred[..colours]
+                .iter()
+                .zip(&mean[..colours])
+                .fold(T::zero(), |acc, (&b, &m)| acc + (b - m) * (b - m))
+                .sqrt()
+        })
+    }
+
+    fn gamut_coverage(&self, gamut: Gamut) -> T
+    where
+        C: Convert<T>,
+    {
+        // Map visible pixels to xy chromaticity through their colour type's own conversion to CIE XYZ
+        let mut points = self
+            .iter()
+            .filter(|pixel| !has_alpha(N) || pixel.components()[N - 1] > T::zero())
+            .filter_map(|pixel| {
+                let xyz = pixel.to_xyz();
+                let sum = xyz.x() + xyz.y() + xyz.z();
+                (sum > T::epsilon()).then(|| [xyz.x() / sum, xyz.y() / sum])
+            })
+            .collect::<Vec<_>>();
+
+        let triangle = gamut.primaries().map(|[x, y]| [cast::<T, _>(x), cast(y)]);
+        let covered = clip_polygon(convex_hull(&mut points), &triangle);
+        polygon_area(&covered) / polygon_area(&triangle)
+    }
+}
+
+/// Helper function to count the colour bins of `n` components quantised into `bins` levels each.
+fn colour_bin_count(bins: usize, n: usize) -> Result<usize, ImageError> {
+    u32::try_from(n)
+        .ok()
+        .and_then(|n| bins.checked_pow(n))
+        .ok_or(ImageError::TooManyBins(bins))
+}
+
+/// Helper function to find the colour bin of a pixel, quantising each 8-bit component into `bins` levels.
+fn colour_bin<C, T, const N: usize>(pixel: &C, bins: usize) -> usize
+where
+    C: Components<T, N> + Copy,
+    T: Float + Send + Sync,
+{
+    pixel
+        .to_bytes()
+        .iter()
+        .fold(0, |index, &value| index * bins + value as usize * bins / 256)
+}
+
+/// Helper function to compute the z component of the cross product of `b - a` and `c - a`.
+fn cross<T: Float>(a: [T; 2], b: [T; 2], c: [T; 2]) -> T {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+/// Helper function to find the anticlockwise convex hull of a set of points by Andrew's monotone chain.
+fn convex_hull<T: Float>(points: &mut [[T; 2]]) -> Vec<[T; 2]> {
+    points.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    let mut hull: Vec<[T; 2]> = Vec::with_capacity(points.len() + 1);
+    let n = points.len();
+    for lower in [true, false] {
+        // Build the lower chain left to right, then the upper chain right to left
+        let start = hull.len();
+        for i in 0..n {
+            let point = points[if lower { i } else { n - 1 - i }];
+            while hull.len() >= start + 2 && cross(hull[hull.len() - 2], hull[hull.len() - 1], point) <= T::zero() {
+                hull.pop();
+            }
+            hull.push(point);
+        }
+        hull.pop();
+    }
+    hull
+}
+
+/// Helper function to clip a polygon to the inside of an anticlockwise convex polygon (Sutherland-Hodgman).
+fn clip_polygon<T: Float>(mut polygon: Vec<[T; 2]>, clip: &[[T; 2]]) -> Vec<[T; 2]> {
+    for (i, &a) in clip.iter().enumerate() {
+        let b = clip[(i + 1) % clip.len()];
+        let input = std::mem::take(&mut polygon);
+        for (j, &p) in input.iter().enumerate() {
+            let q = input[(j + 1) % input.len()];
+            let (dp, dq) = (cross(a, b, p), cross(a, b, q));
+            if dp >= T::zero() {
+                polygon.push(p);
+            }
+            if (dp >= T::zero()) != (dq >= T::zero()) {
+                let t = dp / (dp - dq);
+                polygon.push([p[0] + (q[0] - p[0]) * t, p[1] + (q[1] - p[1]) * t]);
+            }
+        }
+    }
+    polygon
+}
+
+/// Helper function to compute the area of a simple polygon by the shoelace formula.
+fn polygon_area<T: Float>(polygon: &[[T; 2]]) -> T {
+    let twice = (0..polygon.len()).fold(T::zero(), |acc, i| {
+        let (p, q) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+        acc + p[0] * q[1] - q[0] * p[1]
+    });
+    twice.abs() / cast(2)
+}
+
+#[cfg(test)]
+mod tests {
+    use chromatic::{Colour, Convert, Rgb, Srgb};
+    use ndarray::{Array2, array};
+
+    use crate::{Components, Gamut, ImageError, Statistics};
+
+    #[test]
+    fn flat_image_has_one_run_per_row() {
+        let image = Array2::from_elem((3, 5), Rgb::new(0.2_f32, 0.4, 0.6));
+        let stats = image.run_length_stats();
+        assert_eq!((stats.runs, stats.pixels, stats.longest), (3, 15, 5));
+        assert_eq!(stats.mean_length(), 5.0);
+    }
+
+    #[test]
+    fn noisy_image_has_runs_of_length_one() {
+        let image = Array2::from_shape_fn((4, 4), |(row, col)| Rgb::new((row * 4 + col) as f32 / 16.0, 0.0, 0.0));
+        let stats = image.run_length_stats();
+        assert_eq!((stats.runs, stats.longest), (16, 1));
+        assert_eq!(stats.mean_length(), 1.0);
+    }
+
+    #[test]
+    fn solid_region_is_coherent_and_noise_incoherent() {
+        let (black, white) = (Rgb::new(0.0_f32, 0.0, 0.0), Rgb::new(1.0, 1.0, 1.0));
+        let mut image = Array2::from_elem((6, 6), black);
+        for (row, col) in [(0, 0), (0, 4), (2, 2), (4, 0), (5, 5)] {
+            image[[row, col]] = white;
+        }
+
+        let ccv = image.colour_coherence_vector(2, 4).unwrap();
+        assert_eq!(ccv.len(), 8);
+        assert_eq!(ccv[0], (31, 0));
+        assert_eq!(ccv[7], (0, 5));
+    }
+
+    #[test]
+    fn colour_bin_count_overflow_is_an_error() {
+        assert!(matches!(super::colour_bin_count(256, 16), Err(ImageError::TooManyBins(256))));
+        assert_eq!(super::colour_bin_count(4, 3).unwrap(), 64);
+    }
+
+    #[test]
+    fn backprojecting_an_objects_histogram_highlights_it() {
+        let (grey, red) = (Rgb::new(0.5_f32, 0.5, 0.5), Rgb::new(0.9, 0.1, 0.1));
+        let object = Array2::from_elem((2, 2), red);
+        let model = object.colour_histogram(4).unwrap();
+
+        let mut scene = Array2::from_elem((6, 6), grey);
+        scene.slice_mut(ndarray::s![1..3, 3..5]).fill(red);
+        let likelihood = scene.backproject(&model, 4).unwrap();
+        assert!(likelihood.indexed_iter().all(|((row, col), &p)| {
+            let inside = (1..3).contains(&row) && (3..5).contains(&col);
+            p == if inside { 1.0 } else { 0.0 }
+        }));
+    }
+
+    #[test]
+    fn backproject_rejects_a_mismatched_histogram() {
+        let image = Array2::from_elem((2, 2), Rgb::new(0.5_f32, 0.5, 0.5));
+        assert!(matches!(image.backproject(&[1; 16], 4), Err(ImageError::HistogramLength(16))));
+    }
+
+    #[test]
+    fn gamut_coverage_is_zero_for_one_colour_and_high_for_a_rainbow() {
+        let flat = Array2::from_elem((4, 4), Rgb::new(0.9_f32, 0.3, 0.1));
+        assert!(flat.gamut_coverage(Gamut::Srgb) < 1e-6);
+
+        // Sweep the saturated hues around the edge of the RGB cube
+        let rainbow = Array2::from_shape_fn((1, 60), |(_, col)| {
+            let hue = col as f32 / 10.0;
+            let ramp = |offset: f32| (2.0 - ((hue + offset) % 6.0 - 3.0).abs()).clamp(0.0, 1.0);
+            Rgb::new(ramp(3.0), ramp(1.0), ramp(5.0))
+        });
+        let srgb = rainbow.gamut_coverage(Gamut::Srgb);
+        let p3 = rainbow.gamut_coverage(Gamut::DisplayP3);
+        assert!(srgb > 0.95, "{srgb}");
+        assert!(p3 < srgb && p3 > 0.6, "{p3}");
+
+        // Each colour type converts itself, so encoded sRGB and its linear equivalent cover the same area
+        let encoded = array![[
+            Srgb::new(0.8_f32, 0.3, 0.2),
+            Srgb::new(0.2, 0.7, 0.3),
+            Srgb::new(0.3, 0.2, 0.9)
+        ]];
+        let linear = encoded.map(Convert::to_rgb);
+        let (a, b) = (encoded.gamut_coverage(Gamut::Srgb), linear.gamut_coverage(Gamut::Srgb));
+        assert!(a > 0.05 && (a - b).abs() < 1e-4, "{a} {b}");
+    }
+
+    #[test]
+    fn histogram_plot_spikes_each_channel_at_its_value() {
+        let image = Array2::from_elem((4, 4), Rgb::<f32>::from_bytes([200, 0, 0]));
+        let [red, green, _] = image.channel_histograms(4);
+        assert_eq!((red, green), (vec![0, 0, 0, 16], vec![16, 0, 0, 0]));
+
+        let plot = image.histogram_plot::<Rgb<f32>>([32, 256]);
+        // Only the red curve peaks at 200, while green and blue both peak at zero
+        assert_eq!(plot[[0, 200]].components(), [1.0, 0.0, 0.0]);
+        assert_eq!(plot[[0, 0]].components(), [0.0, 1.0, 1.0]);
+        assert_eq!(plot[[0, 100]].components(), [0.0, 0.0, 0.0]);
+        // Away from the spikes all three curves lie along the bottom row
+        assert_eq!(plot[[31, 100]].components(), [1.0, 1.0, 1.0]);
+    }
+}
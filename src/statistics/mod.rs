@@ -0,0 +1,116 @@
+use chromatic::Convert;
+use ndarray::Array2;
+use num_traits::Float;
+
+use crate::{Components, ImageError};
+
+mod arr2;
+
+/// Standard RGB colour spaces, as the triangles their primaries span in CIE 1931 `xy` chromaticity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gamut {
+    /// The sRGB (Rec. 709) primaries.
+    Srgb,
+    /// The Display P3 primaries.
+    DisplayP3,
+}
+
+impl Gamut {
+    /// Chromaticities `[x, y]` of the red, green and blue primaries.
+    pub fn primaries(self) -> [[f64; 2]; 3] {
+        match self {
+            Gamut::Srgb => [[0.64, 0.33], [0.30, 0.60], [0.15, 0.06]],
+            Gamut::DisplayP3 => [[0.680, 0.320], [0.265, 0.690], [0.150, 0.060]],
+        }
+    }
+}
+
+/// Runs of identical consecutive pixels within the rows of an image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunStats {
+    /// Number of runs.
+    pub runs: usize,
+    /// Number of pixels covered by the runs.
+    pub pixels: usize,
+    /// Length of the longest run.
+    pub longest: usize,
+}
+
+impl RunStats {
+    /// Mean number of pixels per run, or zero if there are no runs.
+    pub fn mean_length(&self) -> f64 {
+        if self.runs == 0 {
+            0.0
+        } else {
+            self.pixels as f64 / self.runs as f64
+        }
+    }
+}
+
+/// Trait for computing descriptive statistics of an image.
+pub trait Statistics<C, T, const N: usize>
+where
+    C: Components<T, N> + Clone,
+    T: Float + Send + Sync,
+{
+    /// Compute the colour coherence vector of the image.
+    ///
+    /// Each 8-bit component is quantised into `bins` levels, giving `bins^N` colour bins in row-major component order.
+    /// Pixels are coherent when their 8-connected region of same-bin pixels is larger than `tau`, and each entry of the
+    /// result holds the `(coherent, incoherent)` pixel counts of a bin.
+    ///
+    /// Returns an error if the number of colour bins overflows `usize`.
+    fn colour_coherence_vector(&self, bins: usize, tau: usize) -> Result<Vec<(u32, u32)>, ImageError>;
+
+    /// Count the pixels falling in each colour bin, with bins laid out as in `colour_coherence_vector`.
+    ///
+    /// Returns an error if the number of colour bins overflows `usize`.
+    fn colour_histogram(&self, bins: usize) -> Result<Vec<u32>, ImageError>;
+
+    /// Count the pixels falling in each of `bins` equal bins of each 8-bit component separately.
+    fn channel_histograms(&self, bins: usize) -> [Vec<u32>; N];
+
+    /// Plot the 256-bin histogram of each colour channel as a curve on a black image of the given `[height, width]`.
+    ///
+    /// Colour images plot red, green and blue curves which add where they overlap, and grey images plot a white curve.
+    /// All curves share one vertical scale, with the tallest bin reaching the top row, and alpha is not plotted.
+    fn histogram_plot<D>(&self, size: [usize; 2]) -> Array2<D>
+    where
+        D: Components<T, 3> + Copy;
+
+    /// Measure the runs of pixels with identical components along each row, as a guide to how well the image will
+    /// compress with run-length encoding.
+    ///
+    /// Runs end at the end of each row, so a flat image has one run per row.
+    fn run_length_stats(&self) -> RunStats;
+
+    /// Back-project a model colour histogram onto the image, giving the likelihood that each pixel belongs to the model.
+    ///
+    /// The model is a flattened `bins^N` histogram laid out as by `colour_histogram`: a colour whose components fall in
+    /// bins `b0, b1, ...` is counted at `(b0 * bins + b1) * bins + ...`, so an RGB histogram is a row-major
+    /// `[bins, bins, bins]` array. Each pixel takes its bin's count divided by the largest count, so the result lies in
+    /// `[0, 1]`.
+    ///
+    /// Returns an error if the model length is not `bins^N`.
+    fn backproject(&self, model: &[u32], bins: usize) -> Result<Array2<T>, ImageError>;
+
+    /// Measure how seamlessly the image tiles, as the mean squared component difference across its wrap-around edges.
+    ///
+    /// The last column is compared with the first, and the last row with the first.
+    fn edge_seam_error(&self) -> T;
+
+    /// Estimate the visual saliency of each pixel using frequency-tuned saliency.
+    ///
+    /// Saliency is the distance between each pixel's lightly blurred colour and the mean colour of the image, ignoring
+    /// any alpha channel, so regions standing out from the overall tone score highly.
+    fn saliency(&self) -> Array2<T>;
+
+    /// Estimate the fraction of a colour space's gamut spanned by the image's colours.
+    ///
+    /// Each pixel is converted to CIE XYZ by its colour type and mapped to its CIE 1931 `xy` chromaticity, skipping black
+    /// and fully transparent pixels. The result is the area of the convex hull of those chromaticities lying within the
+    /// gamut's triangle, divided by the triangle's area, so a single colour gives zero.
+    fn gamut_coverage(&self, gamut: Gamut) -> T
+    where
+        C: Convert<T>;
+}
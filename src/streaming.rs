@@ -0,0 +1,101 @@
+//! Row-by-row processing of raw images too large to hold in memory.
+
+use num_traits::Float;
+use std::io::{Error as IoError, ErrorKind, Read, Write};
+
+use crate::Components;
+
+/// Apply a function to every pixel of a raw image, streaming it one row at a time from a reader to a writer.
+///
+/// The raw framing has no header: rows of `width` pixels follow one another, each pixel stored as its `N` interleaved
+/// 8-bit components, until the end of the stream. Only a single row is held in memory at once. Returns the number of
+/// rows processed, or an error if the stream ends part way through a row.
+pub fn process_streaming<C, T, const N: usize, R, W, F>(
+    mut reader: R,
+    mut writer: W,
+    width: usize,
+    mut f: F,
+) -> Result<usize, IoError>
+where
+    C: Components<T, N> + Copy,
+    T: Float + Send + Sync,
+    R: Read,
+    W: Write,
+    F: FnMut(C) -> C,
+{
+    assert!(width > 0, "Row width must be non-zero");
+
+    let mut row = vec![0; width * N];
+    let mut rows = 0;
+    while read_row(&mut reader, &mut row)? {
+        for chunk in row.chunks_exact_mut(N) {
+            let mut bytes = [0; N];
+            bytes.copy_from_slice(chunk);
+            chunk.copy_from_slice(&f(C::from_bytes(bytes)).to_bytes());
+        }
+        writer.write_all(&row)?;
+        rows += 1;
+    }
+    writer.flush()?;
+    Ok(rows)
+}
+
+/// Helper function to fill the buffer with the next row, returning `false` if the stream ended cleanly before it.
+fn read_row<R: Read>(reader: &mut R, row: &mut [u8]) -> Result<bool, IoError> {
+    let mut filled = 0;
+    while filled < row.len() {
+        match reader.read(&mut row[filled..]) {
+            Ok(0) if filled == 0 => return Ok(false),
+            Ok(0) => return Err(IoError::new(ErrorKind::UnexpectedEof, "Stream ended part way through a row")),
+            Ok(count) => filled += count,
+            Err(err) if err.kind() == ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use chromatic::{Colour, Rgb};
+    use ndarray::Array2;
+    use std::io::ErrorKind;
+
+    use crate::{Components, process_streaming};
+
+    fn invert(pixel: Rgb<f32>) -> Rgb<f32> {
+        Rgb::from_components(pixel.components().map(|c| 1.0 - c))
+    }
+
+    #[test]
+    fn streaming_matches_processing_in_memory() {
+        let raw = (0..4 * 5 * 3).map(|i| (i * 17 % 256) as u8).collect::<Vec<_>>();
+        let image = Array2::from_shape_vec(
+            (4, 5),
+            raw.chunks_exact(3)
+                .map(|c| Rgb::<f32>::from_bytes([c[0], c[1], c[2]]))
+                .collect(),
+        )
+        .unwrap();
+
+        let mut streamed = Vec::new();
+        let rows = process_streaming(raw.as_slice(), &mut streamed, 5, invert).unwrap();
+
+        assert_eq!(rows, 4);
+        assert_eq!(
+            streamed,
+            image
+                .map(|&pixel| invert(pixel))
+                .iter()
+                .flat_map(|p| p.to_bytes())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn streaming_rejects_a_partial_row() {
+        let raw = [0_u8; 3 * 5 + 2];
+        let err = process_streaming(raw.as_slice(), Vec::new(), 5, invert).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+}
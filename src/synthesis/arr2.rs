@@ -0,0 +1,347 @@
+use ndarray::Array2;
+use num_traits::Float;
+
+use crate::{
+    Components, Synthesis,
+    components::{cast, distance_squared},
+    dilate,
+    random::SplitMix64,
+};
+
+/// Relative error tolerance when choosing among candidate patches.
+const PATCH_TOLERANCE: f64 = 0.1;
+
+/// Seed of the random nearest-neighbour field initialisation and search used when inpainting.
+const INPAINT_SEED: u64 = 0x5eed;
+
+impl<C, T, const N: usize> Synthesis<C, T, N> for Array2<C>
+where
+    C: Components<T, N> + Copy,
+    T: Float + Send + Sync,
+{
+    fn synthesize_tileable(&self, output_size: [usize; 2], patch: usize, overlap: usize, seed: u64) -> Self {
+        let (sh, sw) = self.dim();
+        let [h, w] = output_size;
+        assert!(overlap < patch, "Overlap must be smaller than the patch");
+        assert!(sh >= patch && sw >= patch, "Image must be at least as large as the patch");
+        let step = patch - overlap;
+        assert!(
+            h > 0 && w > 0 && h % step == 0 && w % step == 0,
+            "Output size must be a multiple of the patch step"
+        );
+
+        let source = self.map(Components::components);
+        let mut canvas = Array2::from_elem((h, w), [T::zero(); N]);
+        let mut filled = Array2::from_elem((h, w), false);
+        let mut rng = SplitMix64::new(seed);
+        let (rows, cols) = (h / step, w / step);
+        for gr in 0..rows {
+            for gc in 0..cols {
+                let origin = (gr * step, gc * step);
+                let at = |i: usize, j: usize| ((origin.0 + i) % h, (origin.1 + j) % w);
+
+                // Score every source patch against the pixels already placed under the footprint
+                let mut candidates = Vec::with_capacity((sh - patch + 1) * (sw - patch + 1));
+                for y in 0..=sh - patch {
+                    for x in 0..=sw - patch {
+                        let mut error = T::zero();
+                        for i in 0..patch {
+                            for j in 0..patch {
+                                let target = at(i, j);
+                                if filled[target] {
+                                    error = error + distance_squared(&source[[y + i, x + j]], &canvas[target]);
+                                }
+                            }
+                        }
+                        candidates.push(((y, x), error));
+                    }
+                }
+                let best = candidates.iter().fold(T::infinity(), |best, &(_, error)| best.min(error));
+                let limit = best * (T::one() + cast(PATCH_TOLERANCE));
+                candidates.retain(|&(_, error)| error <= limit);
+                let (y, x) = candidates[rng.below(candidates.len())].0;
+
+                // Error surface over the footprint, zero where nothing has been placed yet
+                let error = Array2::from_shape_fn((patch, patch), |(i, j)| {
+                    let target = at(i, j);
+                    if filled[target] {
+                        distance_squared(&source[[y + i, x + j]], &canvas[target])
+                    } else {
+                        T::zero()
+                    }
+                });
+
+                // Cut each overlapping side, keeping existing pixels on the far side of the cut
+                let mut keep = Array2::from_elem((patch, patch), false);
+                if overlap > 0 {
+                    let wraps = |index: usize, count: usize| count > 1 && index + 1 == count;
+                    if gc > 0 || wraps(gc, cols) {
+                        let cut = vertical_cut(&error, 0..overlap);
+                        keep.indexed_iter_mut().for_each(|((i, j), k)| *k |= gc > 0 && j < cut[i]);
+                        if wraps(gc, cols) {
+                            let cut = vertical_cut(&error, step..patch);
+                            keep.indexed_iter_mut().for_each(|((i, j), k)| *k |= j > cut[i]);
+                        }
+                    }
+                    if gr > 0 || wraps(gr, rows) {
+                        let transposed = error.t().to_owned();
+                        let cut = vertical_cut(&transposed, 0..overlap);
+                        keep.indexed_iter_mut().for_each(|((i, j), k)| *k |= gr > 0 && i < cut[j]);
+                        if wraps(gr, rows) {
+                            let cut = vertical_cut(&transposed, step..patch);
+                            keep.indexed_iter_mut().for_each(|((i, j), k)| *k |= i > cut[j]);
+                        }
+                    }
+                }
+
+                for ((i, j), &kept) in keep.indexed_iter() {
+                    let target = at(i, j);
+                    if !(kept && filled[target]) {
+                        canvas[target] = source[[y + i, x + j]];
+                        filled[target] = true;
+                    }
+                }
+            }
+        }
+
+        canvas.map(|&c| C::from_components(c))
+    }
+
+    fn inpaint_patchmatch(&self, mask: &Array2<bool>, patch: usize, iterations: usize) -> Self {
+        assert_eq!(mask.dim(), self.dim(), "Mask dimensions must match the image");
+        assert!(patch % 2 == 1, "Patch size must be odd");
+        let (h, w) = self.dim();
+        let radius = patch / 2;
+
+        // Patches centred on a blocked pixel overlap the hole; the rest may serve as sources
+        let blocked = dilate(mask, radius);
+        let valid = Array2::from_shape_fn((h, w), |(y, x)| {
+            y >= radius && x >= radius && y + radius < h && x + radius < w && !blocked[[y, x]]
+        });
+        let sources: Vec<[usize; 2]> = valid.indexed_iter().filter(|&(_, &v)| v).map(|((y, x), _)| [y, x]).collect();
+        assert!(
+            !sources.is_empty(),
+            "Image must contain a patch lying wholly outside the mask"
+        );
+        let targets: Vec<[usize; 2]> = blocked
+            .indexed_iter()
+            .filter(|&(_, &b)| b)
+            .map(|((y, x), _)| [y, x])
+            .collect();
+
+        let mut image = fill_inwards(&self.map(Components::components), mask);
+        let mut rng = SplitMix64::new(INPAINT_SEED);
+        let mut field = Array2::from_elem((h, w), [0; 2]);
+        for &target in &targets {
+            field[target] = sources[rng.below(sources.len())];
+        }
+
+        let offset = |[y, x]: [usize; 2], dy: isize, dx: isize| {
+            let (y, x) = (y.checked_add_signed(dy)?, x.checked_add_signed(dx)?);
+            (y < h && x < w).then_some([y, x])
+        };
+        let span = radius as isize;
+        for iteration in 0..iterations {
+            let mut cost = Array2::from_elem((h, w), T::infinity());
+            for &target in &targets {
+                cost[target] = patch_distance(&image, target, field[target], radius);
+            }
+
+            // Alternate the scan direction, propagating good matches from already visited neighbours
+            let step: isize = if iteration % 2 == 0 { -1 } else { 1 };
+            for k in 0..targets.len() {
+                let target = if step < 0 {
+                    targets[k]
+                } else {
+                    targets[targets.len() - 1 - k]
+                };
+                let mut consider = |candidate: [usize; 2], field: &mut Array2<[usize; 2]>| {
+                    if valid[candidate] {
+                        let distance = patch_distance(&image, target, candidate, radius);
+                        if distance < cost[target] {
+                            cost[target] = distance;
+                            field[target] = candidate;
+                        }
+                    }
+                };
+                for (dy, dx) in [(step, 0), (0, step)] {
+                    let neighbour = offset(target, dy, dx).filter(|&n| blocked[n]);
+                    if let Some(candidate) = neighbour.and_then(|n| offset(field[n], -dy, -dx)) {
+                        consider(candidate, &mut field);
+                    }
+                }
+
+                // Random search in exponentially shrinking windows around the current match
+                let mut window = h.max(w) as isize;
+                while window >= 1 {
+                    let dy = rng.below(2 * window as usize + 1) as isize - window;
+                    let dx = rng.below(2 * window as usize + 1) as isize - window;
+                    if let Some(candidate) = offset(field[target], dy, dx) {
+                        consider(candidate, &mut field);
+                    }
+                    window /= 2;
+                }
+            }
+
+            // Vote, averaging the source pixels each hole pixel is covered by
+            let mut sums = Array2::from_elem((h, w), [T::zero(); N]);
+            let mut counts = Array2::<usize>::zeros((h, w));
+            for &target in &targets {
+                let source = field[target];
+                for dy in -span..=span {
+                    for dx in -span..=span {
+                        if let Some(pixel) = offset(target, dy, dx).filter(|&p| mask[p]) {
+                            let value = image[offset(source, dy, dx).expect("Source patches lie inside the image")];
+                            for (sum, v) in sums[pixel].iter_mut().zip(value) {
+                                *sum = *sum + v;
+                            }
+                            counts[pixel] += 1;
+                        }
+                    }
+                }
+            }
+            for ((index, value), &count) in image.indexed_iter_mut().zip(counts.iter()) {
+                if count > 0 {
+                    *value = sums[index].map(|sum| sum / cast(count));
+                }
+            }
+        }
+
+        image.map(|&c| C::from_components(c))
+    }
+}
+
+/// Helper function to find the minimum-error top-to-bottom path through a band of columns.
+///
+/// Returns the column of the path in each row, moving at most one column between rows.
+fn vertical_cut<T: Float>(error: &Array2<T>, band: std::ops::Range<usize>) -> Vec<usize> {
+    let rows = error.nrows();
+    let mut cost = Array2::from_elem((rows, band.end), T::infinity());
+    for j in band.clone() {
+        cost[[0, j]] = error[[0, j]];
+    }
+    for i in 1..rows {
+        for j in band.clone() {
+            let lo = j.saturating_sub(1).max(band.start);
+            let hi = (j + 1).min(band.end - 1);
+            let previous = (lo..=hi).fold(T::infinity(), |best, k| best.min(cost[[i - 1, k]]));
+            cost[[i, j]] = error[[i, j]] + previous;
+        }
+    }
+
+    // Trace back from the cheapest end point
+    let mut path = vec![0; rows];
+    let mut j = band.clone().fold(band.start, |best, k| {
+        if cost[[rows - 1, k]] < cost[[rows - 1, best]] {
+            k
+        } else {
+            best
+        }
+    });
+    for i in (0..rows).rev() {
+        path[i] = j;
+        if i > 0 {
+            let lo = j.saturating_sub(1).max(band.start);
+            let hi = (j + 1).min(band.end - 1);
+            j = (lo..=hi).fold(lo, |best, k| if cost[[i - 1, k]] < cost[[i - 1, best]] { k } else { best });
+        }
+    }
+    path
+}
+
+/// Helper function to fill the masked pixels from the boundary inwards, averaging each ring from its known neighbours.
+fn fill_inwards<T: Float, const N: usize>(image: &Array2<[T; N]>, mask: &Array2<bool>) -> Array2<[T; N]> {
+    let (h, w) = image.dim();
+    let mut filled = image.clone();
+    let mut known = mask.map(|&m| !m);
+    loop {
+        let ring: Vec<_> = known
+            .indexed_iter()
+            .filter(|&(_, &k)| !k)
+            .filter_map(|((y, x), _)| {
+                let mut sum = [T::zero(); N];
+                let mut count = 0;
+                for ny in y.saturating_sub(1)..(y + 2).min(h) {
+                    for nx in x.saturating_sub(1)..(x + 2).min(w) {
+                        if known[[ny, nx]] {
+                            for (s, &v) in sum.iter_mut().zip(filled[[ny, nx]].iter()) {
+                                *s = *s + v;
+                            }
+                            count += 1;
+                        }
+                    }
+                }
+                (count > 0).then(|| ([y, x], sum.map(|s| s / cast(count))))
+            })
+            .collect();
+        if ring.is_empty() {
+            return filled;
+        }
+        for (index, value) in ring {
+            filled[index] = value;
+            known[index] = true;
+        }
+    }
+}
+
+/// Helper function to measure the squared difference between the patches centred on `target` and `source`.
+///
+/// Target patch pixels falling outside the image are ignored; the source patch must lie wholly inside it.
+fn patch_distance<T: Float, const N: usize>(
+    image: &Array2<[T; N]>,
+    target: [usize; 2],
+    source: [usize; 2],
+    radius: usize,
+) -> T {
+    let (h, w) = image.dim();
+    let mut total = T::zero();
+    for dy in 0..=2 * radius {
+        for dx in 0..=2 * radius {
+            let (ty, tx) = ((target[0] + dy).wrapping_sub(radius), (target[1] + dx).wrapping_sub(radius));
+            if ty < h && tx < w {
+                let (sy, sx) = (source[0] + dy - radius, source[1] + dx - radius);
+                total = total + distance_squared(&image[[ty, tx]], &image[[sy, sx]]);
+            }
+        }
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use chromatic::Grey;
+    use ndarray::{Array2, s};
+
+    use crate::{Components, Statistics, Synthesis};
+
+    #[test]
+    fn inpainting_continues_texture_rather_than_blurring() {
+        let stripe = |col: usize| if col.is_multiple_of(2) { 0.0_f32 } else { 1.0 };
+        let image = Array2::from_shape_fn((16, 16), |(_, col)| Grey::new(stripe(col)));
+        let mut mask = Array2::from_elem((16, 16), false);
+        mask.slice_mut(s![6..10, 6..10]).fill(true);
+
+        let mut damaged = image.clone();
+        damaged.slice_mut(s![6..10, 6..10]).fill(Grey::new(0.5));
+        let inpainted = damaged.inpaint_patchmatch(&mask, 3, 5);
+        for ((row, col), pixel) in inpainted.indexed_iter() {
+            let [grey] = pixel.components();
+            assert!((grey - stripe(col)).abs() < 0.25, "pixel ({row}, {col}) is {grey}");
+        }
+    }
+
+    #[test]
+    fn quilted_textures_have_the_requested_size_and_tile_seamlessly() {
+        // Diagonal waves whose period does not divide the sample, so the sample itself tiles badly
+        let sample = Array2::from_shape_fn((16, 16), |(row, col)| {
+            Grey::new(0.5 + 0.5 * ((row + 2 * col) as f32 * 0.3).sin())
+        });
+
+        let quilted = sample.synthesize_tileable([20, 25], 8, 3, 42);
+        assert_eq!(quilted.dim(), (20, 25));
+        assert!(quilted.edge_seam_error() < sample.edge_seam_error() / 4.0);
+
+        let again = sample.synthesize_tileable([20, 25], 8, 3, 42);
+        assert!(quilted.iter().zip(&again).all(|(a, b)| a.components() == b.components()));
+    }
+}
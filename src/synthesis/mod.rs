@@ -0,0 +1,32 @@
+use ndarray::Array2;
+use num_traits::Float;
+
+use crate::Components;
+
+mod arr2;
+
+/// Trait for generating new image content from an example texture.
+pub trait Synthesis<C, T, const N: usize>
+where
+    C: Components<T, N> + Clone,
+    T: Float + Send + Sync,
+{
+    /// Synthesise a seamlessly tileable texture of the given `[height, width]` by Efros-Freeman image quilting.
+    ///
+    /// Square patches of side `patch` are copied from the image onto a wrap-around canvas, overlapping their neighbours
+    /// by `overlap` pixels. Each patch is picked at random, using the seed, from the candidates whose overlap error is
+    /// within 10% of the best, and is joined along minimum-error boundary cuts. Both output dimensions must be
+    /// non-zero multiples of `patch - overlap`, and the image must be at least `patch` pixels in each dimension.
+    fn synthesize_tileable(&self, output_size: [usize; 2], patch: usize, overlap: usize, seed: u64) -> Array2<C>;
+
+    /// Fill the pixels set in `mask` with texture copied from the rest of the image.
+    ///
+    /// The hole is first filled from its boundary inwards, then refined for the given number of `iterations`. Each
+    /// iteration matches every patch (of odd side `patch`) overlapping the hole to its most similar patch lying wholly
+    /// outside it, using a PatchMatch nearest-neighbour search, and each hole pixel takes the average of its matches.
+    /// Pixels outside the mask are left unchanged.
+    ///
+    /// The mask is a boolean array, as taken by the `morphology` functions and returned by `foreground_mask`, rather
+    /// than a mask image; a grey-alpha mask image converts with `mask.map(|pixel| pixel.components()[1] > 0.5)`.
+    fn inpaint_patchmatch(&self, mask: &Array2<bool>, patch: usize, iterations: usize) -> Array2<C>;
+}
@@ -0,0 +1,605 @@
+use ndarray::{Array2, ArrayView2, s};
+use num_traits::Float;
+#[cfg(feature = "rand")]
+use rand::{Rng, seq::SliceRandom};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+use crate::{
+    Adjacency, Compare, Components, Tiles,
+    components::{cast, mean, nearest, premultiply, same_pixels, unpremultiply},
+};
+
+/// FNV-1a 64-bit offset basis.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// FNV-1a 64-bit prime.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+impl<C, T, const N: usize> Tiles<C, T, N> for Array2<C>
+where
+    C: Components<T, N> + Copy,
+    T: Float + Send + Sync,
+{
+    fn tiles(&self, tile_size: [usize; 2]) -> Array2<Array2<C>> {
+        check_tile_size(self.dim(), tile_size);
+        let [th, tw] = tile_size;
+        let (h, w) = self.dim();
+
+        let mut tiles = self.exact_chunks((th, tw)).into_iter().map(|tile| tile.to_owned());
+        Array2::from_shape_simple_fn((h / th, w / tw), || tiles.next().expect("Tile count matches grid size"))
+    }
+
+    fn tiles_iter<'a>(&'a self, tile_size: [usize; 2]) -> impl Iterator<Item = ([usize; 2], Array2<C>)>
+    where
+        C: 'a,
+    {
+        self.tile_views(tile_size).map(|(position, view)| (position, view.to_owned()))
+    }
+
+    fn tile_views<'a>(&'a self, tile_size: [usize; 2]) -> impl Iterator<Item = ([usize; 2], ArrayView2<'a, C>)>
+    where
+        C: 'a,
+    {
+        check_tile_size(self.dim(), tile_size);
+        let [th, tw] = tile_size;
+        let (rows, cols) = (self.nrows() / th, self.ncols() / tw);
+
+        (0..rows).flat_map(move |row| {
+            (0..cols).map(move |col| ([row, col], self.slice(s![row * th..(row + 1) * th, col * tw..(col + 1) * tw])))
+        })
+    }
+
+    fn from_tiles(tiles: &Array2<Array2<C>>) -> Self {
+        let (rows, cols) = tiles.dim();
+        let (th, tw) = tiles.first().map_or((0, 0), Array2::dim);
+        assert!(
+            tiles.iter().all(|tile| tile.dim() == (th, tw)),
+            "Tiles must all be the same size"
+        );
+
+        Array2::from_shape_fn((rows * th, cols * tw), |(row, col)| {
+            tiles[[row / th, col / tw]][[row % th, col % tw]]
+        })
+    }
+
+    fn tiles_premultiplied(&self, tile_size: [usize; 2]) -> Array2<Array2<C>> {
+        self.map(|pixel| C::from_components(premultiply(pixel.components())))
+            .tiles(tile_size)
+    }
+
+    fn from_tiles_premultiplied(tiles: &Array2<Array2<C>>) -> Self {
+        Self::from_tiles(tiles).mapv(|pixel| C::from_components(unpremultiply(pixel.components())))
+    }
+
+    #[cfg(feature = "rand")]
+    fn shuffle_tiles<R: Rng + ?Sized>(&mut self, tile_size: [usize; 2], rng: &mut R) {
+        let tiles = self.tiles(tile_size);
+        let mut order = (0..tiles.len()).collect::<Vec<_>>();
+        order.shuffle(rng);
+
+        let sources = tiles.iter().collect::<Vec<_>>();
+        for (mut target, source) in self.exact_chunks_mut((tile_size[0], tile_size[1])).into_iter().zip(order) {
+            target.assign(sources[source]);
+        }
+    }
+
+    fn unique_tiles(&self, tile_size: [usize; 2]) -> Vec<(Array2<C>, usize)> {
+        let adjacency = self.tile_adjacencies(tile_size);
+        adjacency.tiles.into_iter().zip(adjacency.frequencies).collect()
+    }
+
+    #[cfg(feature = "rayon")]
+    fn par_unique_tiles(&self, tile_size: [usize; 2]) -> Vec<(Array2<C>, usize)>
+    where
+        C: Send + Sync,
+    {
+        let views = self.tile_views(tile_size).map(|(_, tile)| tile).collect::<Vec<_>>();
+
+        // Hash and tally each thread's tiles as `(first index, tile, count)` under their hashes, then merge the tallies
+        let mut counts = views
+            .into_par_iter()
+            .enumerate()
+            .fold(TileCounts::new, |mut counts, (index, tile)| {
+                let hash = tile_hash(&tile);
+                tally(&mut counts, hash, (index, tile, 1));
+                counts
+            })
+            .reduce(TileCounts::new, |mut counts, other| {
+                for (hash, entries) in other {
+                    for entry in entries {
+                        tally(&mut counts, hash, entry);
+                    }
+                }
+                counts
+            })
+            .into_values()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        // Restore the order of first appearance used by `unique_tiles`, copying only the unique tiles
+        counts.sort_unstable_by_key(|&(index, _, _)| index);
+        counts.into_iter().map(|(_, tile, count)| (tile.to_owned(), count)).collect()
+    }
+
+    fn tile_adjacencies(&self, tile_size: [usize; 2]) -> Adjacency<C> {
+        check_tile_size(self.dim(), tile_size);
+        let grid_dim = (self.nrows() / tile_size[0], self.ncols() / tile_size[1]);
+
+        // Identify each tile by exact component equality, only comparing tiles whose hashes match and only copying
+        // the first occurrence of each
+        let mut tiles: Vec<Array2<C>> = Vec::new();
+        let mut frequencies = Vec::new();
+        let mut candidates: HashMap<u64, Vec<usize>> = HashMap::new();
+        let indices = self
+            .tile_views(tile_size)
+            .map(|(_, tile)| {
+                let matches = candidates.entry(tile_hash(&tile)).or_default();
+                match matches.iter().copied().find(|&index| same_pixels(&tiles[index], &tile)) {
+                    Some(index) => {
+                        frequencies[index] += 1;
+                        index
+                    }
+                    None => {
+                        matches.push(tiles.len());
+                        tiles.push(tile.to_owned());
+                        frequencies.push(1);
+                        tiles.len() - 1
+                    }
+                }
+            })
+            .collect();
+        let grid = Array2::from_shape_vec(grid_dim, indices).expect("Tile count matches grid size");
+
+        // Tally neighbours in each direction
+        let n = tiles.len();
+        let mut north = Array2::zeros((n, n));
+        let mut south = Array2::zeros((n, n));
+        let mut east = Array2::zeros((n, n));
+        let mut west = Array2::zeros((n, n));
+        let (rows, cols) = grid.dim();
+        for ((row, col), &tile) in grid.indexed_iter() {
+            if row > 0 {
+                north[[tile, grid[[row - 1, col]]]] += 1;
+            }
+            if row + 1 < rows {
+                south[[tile, grid[[row + 1, col]]]] += 1;
+            }
+            if col + 1 < cols {
+                east[[tile, grid[[row, col + 1]]]] += 1;
+            }
+            if col > 0 {
+                west[[tile, grid[[row, col - 1]]]] += 1;
+            }
+        }
+
+        Adjacency {
+            tiles,
+            frequencies,
+            grid,
+            north,
+            south,
+            east,
+            west,
+        }
+    }
+
+    fn tile_hashes(&self, tile_size: [usize; 2]) -> Array2<u64> {
+        check_tile_size(self.dim(), tile_size);
+        let grid_dim = (self.nrows() / tile_size[0], self.ncols() / tile_size[1]);
+        let hashes = self.tile_views(tile_size).map(|(_, tile)| tile_hash(&tile)).collect();
+        Array2::from_shape_vec(grid_dim, hashes).expect("Tile count matches grid size")
+    }
+
+    fn tile_dhashes(&self, tile_size: [usize; 2]) -> Array2<u64> {
+        self.tiles(tile_size).map(Compare::difference_hash)
+    }
+
+    fn tile_modes(&self, tile_size: [usize; 2]) -> Self {
+        check_tile_size(self.dim(), tile_size);
+        let [th, tw] = tile_size;
+
+        let mut counts: HashMap<[[u8; 12]; N], (usize, usize)> = HashMap::new();
+        let modes = self
+            .tile_views(tile_size)
+            .map(|(_, tile)| {
+                // Count each colour, remembering where it first appears
+                counts.clear();
+                for (index, pixel) in tile.iter().enumerate() {
+                    let key = pixel.components().map(component_bytes);
+                    counts.entry(key).or_insert((0, index)).0 += 1;
+                }
+                let &(_, first) = counts
+                    .values()
+                    .max_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)))
+                    .expect("Tiles are non-empty");
+                tile[[first / tw, first % tw]]
+            })
+            .collect::<Vec<_>>();
+        Array2::from_shape_vec((self.nrows() / th, self.ncols() / tw), modes).expect("One mode per tile")
+    }
+
+    fn photomosaic(&self, tile_size: [usize; 2], library: &[Self]) -> Self {
+        check_tile_size(self.dim(), tile_size);
+        assert!(!library.is_empty(), "Library must contain at least one image");
+        assert!(
+            library.iter().all(|image| image.dim() == (tile_size[0], tile_size[1])),
+            "Library images must match the tile size"
+        );
+
+        let palette = library.iter().map(mean).collect::<Vec<_>>();
+        let mut mosaic = self.clone();
+        for (tile, mut target) in self
+            .exact_chunks((tile_size[0], tile_size[1]))
+            .into_iter()
+            .zip(mosaic.exact_chunks_mut((tile_size[0], tile_size[1])))
+        {
+            target.assign(&library[nearest(&palette, &mean(tile))]);
+        }
+        mosaic
+    }
+
+    fn quadtree_tiles(&self, max_tile: usize, min_tile: usize, variance_threshold: T) -> Vec<([usize; 2], Array2<C>)> {
+        assert!(
+            min_tile > 0 && min_tile <= max_tile,
+            "Tile sizes must be non-zero and ordered"
+        );
+        let (h, w) = self.dim();
+
+        let mut tiles = Vec::new();
+        for row in (0..h).step_by(max_tile) {
+            for col in (0..w).step_by(max_tile) {
+                let size = [max_tile.min(h - row), max_tile.min(w - col)];
+                subdivide(self, [row, col], size, min_tile, variance_threshold, &mut tiles);
+            }
+        }
+        tiles
+    }
+}
+
+/// Helper function to hash the full-precision components of a tile with FNV-1a.
+fn tile_hash<C, T, const N: usize>(tile: &ArrayView2<C>) -> u64
+where
+    C: Components<T, N>,
+    T: Float + Send + Sync,
+{
+    tile.iter()
+        .flat_map(|pixel| pixel.components())
+        .flat_map(component_bytes)
+        .fold(FNV_OFFSET_BASIS, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+        })
+}
+
+/// Helper function to get bytes identifying a component's value, so equal components always give equal bytes.
+fn component_bytes<T: Float>(x: T) -> [u8; 12] {
+    // Adding zero turns negative zero into positive zero, which compares equal to it
+    let (mantissa, exponent, sign) = (x + T::zero()).integer_decode();
+    let mut bytes = [0; 12];
+    bytes[..8].copy_from_slice(&mantissa.to_le_bytes());
+    bytes[8..10].copy_from_slice(&exponent.to_le_bytes());
+    bytes[10] = sign as u8;
+    bytes
+}
+
+/// Helper function to check that an image divides exactly into tiles of the given size.
+fn check_tile_size((h, w): (usize, usize), [th, tw]: [usize; 2]) {
+    assert!(th > 0 && tw > 0, "Tile size must be non-zero");
+    assert!(h % th == 0, "Image height must be a multiple of the tile height");
+    assert!(w % tw == 0, "Image width must be a multiple of the tile width");
+}
+
+/// Helper function to emit a region as a tile, or quarter it if it is too varied and its quarters are large enough.
+fn subdivide<C, T, const N: usize>(
+    image: &Array2<C>,
+    origin: [usize; 2],
+    size: [usize; 2],
+    min_tile: usize,
+    variance_threshold: T,
+    tiles: &mut Vec<([usize; 2], Array2<C>)>,
+) where
+    C: Components<T, N> + Copy,
+    T: Float + Send + Sync,
+{
+    let region = image.slice(s![origin[0]..origin[0] + size[0], origin[1]..origin[1] + size[1]]);
+    let half = [size[0] / 2, size[1] / 2];
+    if half[0] < min_tile || half[1] < min_tile || variance(&region) <= variance_threshold {
+        tiles.push((origin, region.to_owned()));
+        return;
+    }
+
+    for (dr, height) in [(0, half[0]), (half[0], size[0] - half[0])] {
+        for (dc, width) in [(0, half[1]), (half[1], size[1] - half[1])] {
+            subdivide(
+                image,
+                [origin[0] + dr, origin[1] + dc],
+                [height, width],
+                min_tile,
+                variance_threshold,
+                tiles,
+            );
+        }
+    }
+}
+
+/// Helper function to compute the variance of each component over a region, averaged across components.
+fn variance<C, T, const N: usize>(region: &ArrayView2<C>) -> T
+where
+    C: Components<T, N> + Copy,
+    T: Float + Send + Sync,
+{
+    let average = mean(region.iter());
+    let total = region.iter().fold(T::zero(), |acc, pixel| {
+        let components = pixel.components();
+        acc + (0..N).fold(T::zero(), |acc, n| acc + (components[n] - average[n]).powi(2))
+    });
+    total / cast(region.len() * N)
+}
+
+/// Unique tiles grouped by hash, each with the index of its first appearance and its number of occurrences.
+#[cfg(feature = "rayon")]
+type TileCounts<'a, C> = HashMap<u64, Vec<(usize, ArrayView2<'a, C>, usize)>>;
+
+/// Helper function to add occurrences of a tile to the counts, keeping the earliest index at which it appears.
+#[cfg(feature = "rayon")]
+fn tally<'a, C, T, const N: usize>(
+    counts: &mut TileCounts<'a, C>,
+    hash: u64,
+    (index, tile, count): (usize, ArrayView2<'a, C>, usize),
+) where
+    C: Components<T, N>,
+    T: Float + Send + Sync,
+{
+    let entries = counts.entry(hash).or_default();
+    match entries.iter_mut().find(|(_, other, _)| same_pixels(other, &tile)) {
+        Some((first, _, total)) => {
+            *first = (*first).min(index);
+            *total += count;
+        }
+        None => entries.push((index, tile, count)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chromatic::{Rgb, RgbAlpha};
+    use ndarray::{Array2, s};
+
+    use crate::{
+        BorderMode, Components, Filter, Tiles,
+        components::{mean, same_pixels},
+    };
+
+    #[test]
+    fn tile_modes_picks_the_dominant_colour() {
+        let red = Rgb::new(1.0_f32, 0.0, 0.0);
+        let blue = Rgb::new(0.0, 0.0, 1.0);
+        let mut image = Array2::from_elem((2, 4), red);
+        image[[0, 0]] = blue;
+        image[[0, 2]] = blue;
+        image[[1, 2]] = blue;
+        image[[1, 3]] = blue;
+
+        let modes = image.tile_modes([2, 2]);
+        assert_eq!(modes.dim(), (1, 2));
+        assert_eq!(modes[[0, 0]].components(), red.components());
+        assert_eq!(modes[[0, 1]].components(), blue.components());
+    }
+
+    #[test]
+    fn tile_modes_distinguishes_colours_closer_than_a_byte() {
+        let a = Rgb::new(0.5_f32, 0.5, 0.5);
+        let b = Rgb::new(0.501, 0.5, 0.5);
+        let image = Array2::from_shape_vec((1, 3), vec![a, b, b]).unwrap();
+
+        let modes = image.tile_modes([1, 3]);
+        assert_eq!(modes[[0, 0]].components(), b.components());
+    }
+
+    #[test]
+    fn tile_adjacencies_count_neighbours_in_each_direction() {
+        let (a, b) = (Rgb::new(1.0_f32, 0.0, 0.0), Rgb::new(0.0, 0.0, 1.0));
+        // Tile grid of A B A over B B A, with 2x2 tiles
+        let layout = [[a, b, a], [b, b, a]];
+        let image = Array2::from_shape_fn((4, 6), |(row, col)| layout[row / 2][col / 2]);
+
+        let adjacency = image.tile_adjacencies([2, 2]);
+        assert_eq!(adjacency.tiles.len(), 2);
+        assert_eq!(adjacency.frequencies, [3, 3]);
+        assert_eq!(adjacency.grid, ndarray::array![[0, 1, 0], [1, 1, 0]]);
+        assert_eq!(adjacency.east, ndarray::array![[0, 1], [2, 1]]);
+        assert_eq!(adjacency.west, ndarray::array![[0, 2], [1, 1]]);
+        assert_eq!(adjacency.south, ndarray::array![[1, 1], [0, 1]]);
+        assert_eq!(adjacency.north, ndarray::array![[1, 0], [1, 1]]);
+
+        let unique = image.unique_tiles([2, 2]);
+        assert_eq!(unique.iter().map(|(_, count)| *count).collect::<Vec<_>>(), [3, 3]);
+        assert!(unique[0].0.iter().all(|pixel| pixel.components() == a.components()));
+    }
+
+    #[test]
+    fn photomosaic_picks_the_closest_library_tiles() {
+        let tile = |red: f32, blue: f32| Array2::from_elem((2, 2), Rgb::new(red, 0.1, blue));
+        let library = [tile(0.5, 0.5), tile(0.9, 0.1), tile(0.7, 0.3), tile(0.1, 0.9), tile(0.3, 0.7)];
+        let mut image = Array2::from_elem((2, 4), Rgb::new(1.0_f32, 0.0, 0.0));
+        image.slice_mut(s![.., 2..]).fill(Rgb::new(0.0, 0.0, 1.0));
+
+        let mosaic = image.photomosaic([2, 2], &library);
+        assert_eq!(mosaic.dim(), (2, 4));
+        assert!(
+            mosaic
+                .slice(s![.., ..2])
+                .iter()
+                .all(|pixel| pixel.components() == [0.9, 0.1, 0.1])
+        );
+        assert!(
+            mosaic
+                .slice(s![.., 2..])
+                .iter()
+                .all(|pixel| pixel.components() == [0.1, 0.1, 0.9])
+        );
+    }
+
+    #[test]
+    fn quadtree_tiles_split_only_detailed_regions() {
+        let flat = Array2::from_elem((16, 16), Rgb::new(0.5_f32, 0.5, 0.5));
+        let tiles = flat.quadtree_tiles(8, 2, 0.001);
+        assert_eq!(
+            tiles.iter().map(|(origin, _)| *origin).collect::<Vec<_>>(),
+            [[0, 0], [0, 8], [8, 0], [8, 8]]
+        );
+
+        // Fine noise in the top-left quarter only
+        let mut detailed = flat.clone();
+        for ((row, col), pixel) in detailed.slice_mut(s![..8, ..8]).indexed_iter_mut() {
+            let grey = if (row + col).is_multiple_of(2) { 0.0 } else { 1.0 };
+            *pixel = Rgb::new(grey, grey, grey);
+        }
+        let tiles = detailed.quadtree_tiles(8, 2, 0.001);
+        let small = tiles.iter().filter(|(_, tile)| tile.dim() == (2, 2)).count();
+        let large = tiles.iter().filter(|(_, tile)| tile.dim() == (8, 8)).count();
+        assert_eq!((small, large, tiles.len()), (16, 3, 19));
+        assert!(
+            tiles
+                .iter()
+                .all(|([row, col], tile)| tile.dim() == (8, 8) || (*row < 8 && *col < 8))
+        );
+    }
+
+    #[test]
+    fn tile_dhashes_differ_only_where_the_images_differ() {
+        let image = Array2::from_shape_fn((32, 48), |(row, col)| {
+            let value = ((row * 7 + col * 13) % 29) as f32 / 28.0;
+            Rgb::new(value, 1.0 - value, 0.5)
+        });
+        let mut edited = image.clone();
+        for ((row, col), pixel) in edited.slice_mut(s![16.., 16..32]).indexed_iter_mut() {
+            let value = ((row * 3 + col * 5) % 11) as f32 / 10.0;
+            *pixel = Rgb::new(value, value, value);
+        }
+
+        let (before, after) = (image.tile_dhashes([16, 16]), edited.tile_dhashes([16, 16]));
+        assert_eq!(before.dim(), (2, 3));
+        for ((row, col), hash) in before.indexed_iter() {
+            assert_eq!(*hash == after[[row, col]], [row, col] != [1, 1], "tile ({row}, {col})");
+        }
+    }
+
+    #[test]
+    fn tile_hashes_and_unique_tiles_use_full_precision() {
+        let mut image = Array2::from_elem((2, 6), Rgb::new(0.5_f32, 0.5, 0.5));
+        image[[0, 4]] = Rgb::new(0.5, 0.5, 0.500_001);
+
+        let hashes = image.tile_hashes([2, 2]);
+        assert_eq!(hashes[[0, 0]], hashes[[0, 1]]);
+        assert_ne!(hashes[[0, 0]], hashes[[0, 2]]);
+
+        let unique = image.unique_tiles([2, 2]);
+        assert_eq!(unique.iter().map(|(_, count)| *count).collect::<Vec<_>>(), [2, 1]);
+        assert_eq!(unique[1].0[[0, 0]].components(), [0.5, 0.5, 0.500_001]);
+    }
+
+    #[test]
+    fn tile_iterators_match_the_tile_grid_lazily() {
+        let image = Array2::from_shape_fn((4, 6), |(row, col)| Rgb::new(row as f32 / 3.0, col as f32 / 5.0, 0.0));
+        let grid = image.tiles([2, 3]);
+
+        let owned = image.tiles_iter([2, 3]).collect::<Vec<_>>();
+        assert_eq!(
+            owned.iter().map(|(position, _)| *position).collect::<Vec<_>>(),
+            [[0, 0], [0, 1], [1, 0], [1, 1]]
+        );
+        for ([row, col], tile) in &owned {
+            assert!(same_pixels(tile, &grid[[*row, *col]]));
+        }
+
+        // Read-only scans can stop at the first matching view
+        let found = image.tile_views([2, 3]).find(|(_, view)| view[[0, 0]].components()[1] > 0.5);
+        assert_eq!(found.map(|(position, _)| position), Some([0, 1]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Image width must be a multiple of the tile width")]
+    fn tiles_iter_checks_divisibility_up_front() {
+        let image = Array2::from_elem((4, 6), Rgb::new(0.0_f32, 0.0, 0.0));
+        let _ = image.tiles_iter([2, 4]);
+    }
+
+    #[test]
+    fn premultiplied_tiles_filter_without_colour_bleed() {
+        // Opaque orange beside transparent pixels holding stray blue colour, inside each tile
+        let image = Array2::from_shape_fn((4, 8), |(_, col)| {
+            if col % 4 < 2 {
+                RgbAlpha::new(1.0_f32, 0.5, 0.0, 1.0)
+            } else {
+                RgbAlpha::new(0.0, 0.0, 1.0, 0.0)
+            }
+        });
+        let box_blur = |tile: &Array2<RgbAlpha<f32>>| {
+            tile.map_with_neighbours(1, BorderMode::Clamp, |window| RgbAlpha::from_components(mean(window.iter())))
+        };
+
+        let tiles = image.tiles_premultiplied([4, 4]).map(box_blur);
+        let blurred = Array2::from_tiles_premultiplied(&tiles);
+        let [red, green, blue, alpha] = blurred[[1, 2]].components();
+        assert!(alpha > 0.0 && alpha < 1.0);
+        assert!((red - 1.0).abs() < 1e-6 && (green - 0.5).abs() < 1e-6 && blue == 0.0);
+
+        let straight = Array2::from_tiles(&image.tiles([4, 4]).map(box_blur));
+        assert!(straight[[1, 2]].components()[2] > 0.1);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_unique_tiles_matches_unique_tiles() {
+        let image = Array2::from_shape_fn((24, 36), |(row, col)| {
+            let shade = ((row / 3 * 7 + col / 3 * 3) % 5) as f32 / 4.0;
+            Rgb::new(shade, 1.0 - shade, 0.5)
+        });
+
+        let sequential = image.unique_tiles([3, 3]);
+        let parallel = image.par_unique_tiles([3, 3]);
+        assert_eq!(sequential.len(), 5);
+        assert_eq!(parallel.len(), sequential.len());
+        for ((a, a_count), (b, b_count)) in parallel.iter().zip(&sequential) {
+            assert!(same_pixels(a, b));
+            assert_eq!(a_count, b_count);
+        }
+        assert_eq!(parallel.iter().map(|(_, count)| count).sum::<usize>(), 8 * 12);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn shuffle_tiles_is_a_reproducible_permutation() {
+        use rand::{SeedableRng, rngs::StdRng};
+
+        // Give each 2x3 tile its own shade, so tiles are identified by any of their pixels
+        let image = Array2::from_shape_fn((8, 12), |(row, col)| {
+            let shade = (row / 2 * 4 + col / 3) as f32 / 16.0;
+            Rgb::new(shade, shade, shade)
+        });
+        let shuffle = |seed| {
+            let mut shuffled = image.clone();
+            shuffled.shuffle_tiles([2, 3], &mut StdRng::seed_from_u64(seed));
+            shuffled
+        };
+
+        let shuffled = shuffle(7);
+        assert!(same_pixels(&shuffled, &shuffle(7)));
+        assert!(!same_pixels(&shuffled, &image));
+
+        let tiles = shuffled.tiles([2, 3]);
+        assert!(
+            tiles
+                .iter()
+                .all(|tile| tile.iter().all(|pixel| pixel.components() == tile[[0, 0]].components()))
+        );
+        let mut shades = tiles
+            .iter()
+            .map(|tile| (tile[[0, 0]].components()[0] * 16.0) as usize)
+            .collect::<Vec<_>>();
+        shades.sort_unstable();
+        assert_eq!(shades, (0..16).collect::<Vec<_>>());
+    }
+}
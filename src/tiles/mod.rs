@@ -0,0 +1,120 @@
+use ndarray::{Array2, ArrayView2};
+use num_traits::Float;
+#[cfg(feature = "rand")]
+use rand::Rng;
+
+use crate::Components;
+
+mod arr2;
+
+/// Adjacency statistics between the unique tiles of an image.
+///
+/// Tile identities are indices into `tiles`, and each directional matrix is indexed as `[tile, neighbour]`,
+/// counting how many times `neighbour` appears on that side of `tile`.
+#[derive(Debug, Clone)]
+pub struct Adjacency<C> {
+    /// Unique tiles, in order of first appearance (row-major).
+    pub tiles: Vec<Array2<C>>,
+    /// Number of occurrences of each unique tile.
+    pub frequencies: Vec<usize>,
+    /// Unique tile index of each cell in the tile grid.
+    pub grid: Array2<usize>,
+    /// Counts of tiles found directly above each tile.
+    pub north: Array2<usize>,
+    /// Counts of tiles found directly below each tile.
+    pub south: Array2<usize>,
+    /// Counts of tiles found directly to the right of each tile.
+    pub east: Array2<usize>,
+    /// Counts of tiles found directly to the left of each tile.
+    pub west: Array2<usize>,
+}
+
+/// Trait for splitting an image into a grid of equally sized tiles.
+///
+/// Tile sizes are given as `[height, width]`, and the image dimensions must be exact multiples of them.
+pub trait Tiles<C, T, const N: usize>
+where
+    C: Components<T, N> + Clone,
+    T: Float + Send + Sync,
+{
+    /// Split the image into a grid of tiles.
+    fn tiles(&self, tile_size: [usize; 2]) -> Array2<Array2<C>>;
+
+    /// Lazily iterate over the tiles in row-major order, paired with their `[row, column]` position in the tile grid.
+    fn tiles_iter<'a>(&'a self, tile_size: [usize; 2]) -> impl Iterator<Item = ([usize; 2], Array2<C>)>
+    where
+        C: 'a;
+
+    /// Lazily iterate over borrowed views of the tiles in row-major order, paired with their `[row, column]` position in
+    /// the tile grid.
+    fn tile_views<'a>(&'a self, tile_size: [usize; 2]) -> impl Iterator<Item = ([usize; 2], ArrayView2<'a, C>)>
+    where
+        C: 'a;
+
+    /// Assemble a grid of equally sized tiles back into a single image, inverting `tiles`.
+    fn from_tiles(tiles: &Array2<Array2<C>>) -> Array2<C>;
+
+    /// Split the image into a grid of tiles holding premultiplied-alpha components.
+    ///
+    /// This suits custom component-wise processing of tiles, such as averaging or interpolation, which would otherwise
+    /// bleed the colour of transparent pixels. The library's own filters and resampling already premultiply internally,
+    /// so should be given straight-alpha tiles from `tiles`. Colours without an alpha channel are unchanged.
+    fn tiles_premultiplied(&self, tile_size: [usize; 2]) -> Array2<Array2<C>>;
+
+    /// Assemble a grid of premultiplied-alpha tiles back into a single straight-alpha image, inverting
+    /// `tiles_premultiplied`.
+    ///
+    /// Fully transparent pixels are returned with zero colour.
+    fn from_tiles_premultiplied(tiles: &Array2<Array2<C>>) -> Array2<C>;
+
+    /// Randomly permute the positions of the tiles in place, so every tile appears exactly once in the result.
+    ///
+    /// Seeded generators give reproducible shuffles.
+    #[cfg(feature = "rand")]
+    fn shuffle_tiles<R: Rng + ?Sized>(&mut self, tile_size: [usize; 2], rng: &mut R);
+
+    /// Find the unique tiles of the image, paired with their number of occurrences.
+    ///
+    /// Tiles are the same when every pixel has equal components.
+    fn unique_tiles(&self, tile_size: [usize; 2]) -> Vec<(Array2<C>, usize)>;
+
+    /// Find the unique tiles of the image and their number of occurrences in parallel, giving the same result as
+    /// `unique_tiles`.
+    ///
+    /// Each thread tallies its share of the tiles into its own map, and the maps are merged at the end.
+    #[cfg(feature = "rayon")]
+    fn par_unique_tiles(&self, tile_size: [usize; 2]) -> Vec<(Array2<C>, usize)>
+    where
+        C: Send + Sync;
+
+    /// Count which unique tiles appear to the north, south, east and west of each unique tile.
+    fn tile_adjacencies(&self, tile_size: [usize; 2]) -> Adjacency<C>;
+
+    /// Compute a stable 64-bit FNV-1a hash of each tile's components.
+    ///
+    /// Identical tiles always share a hash, so hashes are a cheap first test of tile equality.
+    fn tile_hashes(&self, tile_size: [usize; 2]) -> Array2<u64>;
+
+    /// Compute the perceptual difference hash of each tile, so differing regions of two images can be located
+    /// by comparing their hash grids.
+    fn tile_dhashes(&self, tile_size: [usize; 2]) -> Array2<u64>;
+
+    /// Reduce each tile to its most frequent colour, giving an image with one pixel per tile.
+    ///
+    /// Colours are counted by their exact components, and ties go to the colour appearing first in row-major order.
+    /// Unlike averaging, this keeps the hard colours of pixel art.
+    fn tile_modes(&self, tile_size: [usize; 2]) -> Array2<C>;
+
+    /// Replace each tile with the library image whose mean colour is closest to the tile's mean colour.
+    ///
+    /// Every library image must have the tile size, and the library must not be empty.
+    fn photomosaic(&self, tile_size: [usize; 2], library: &[Array2<C>]) -> Array2<C>;
+
+    /// Split the image into square tiles of varying size, paired with their `[row, column]` origins.
+    ///
+    /// The image is first covered by tiles of side `max_tile`, clipped at the right and bottom edges, and any tile
+    /// whose mean component variance exceeds `variance_threshold` is recursively quartered while its halves are at least
+    /// `min_tile`. Flat regions therefore stay as large tiles while detailed regions become many small ones. Unlike the
+    /// other tiling methods, the image dimensions need not be multiples of the tile sizes.
+    fn quadtree_tiles(&self, max_tile: usize, min_tile: usize, variance_threshold: T) -> Vec<([usize; 2], Array2<C>)>;
+}
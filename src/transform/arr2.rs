@@ -0,0 +1,570 @@
+use ndarray::{Array2, s};
+use num_traits::Float;
+
+use crate::{
+    Components, Composite, Connectivity, Filter, ImageError, Interpolation, Statistics, Transform,
+    components::{cast, has_alpha, premultiply, same_pixels, unpremultiply},
+    label_components,
+};
+
+impl<C, T, const N: usize> Transform<C, T, N> for Array2<C>
+where
+    C: Components<T, N> + Copy,
+    T: Float + Send + Sync,
+{
+    fn rotate_clockwise(&self) -> Self {
+        let (h, w) = self.dim();
+        Array2::from_shape_fn((w, h), |(row, col)| self[[h - 1 - col, row]])
+    }
+
+    fn rotate_anticlockwise(&self) -> Self {
+        let (h, w) = self.dim();
+        Array2::from_shape_fn((w, h), |(row, col)| self[[col, w - 1 - row]])
+    }
+
+    fn rotate_half(&self) -> Self {
+        let (h, w) = self.dim();
+        Array2::from_shape_fn((h, w), |(row, col)| self[[h - 1 - row, w - 1 - col]])
+    }
+
+    fn flip_horizontal(&self) -> Self {
+        let (h, w) = self.dim();
+        Array2::from_shape_fn((h, w), |(row, col)| self[[row, w - 1 - col]])
+    }
+
+    fn flip_vertical(&self) -> Self {
+        let (h, w) = self.dim();
+        Array2::from_shape_fn((h, w), |(row, col)| self[[h - 1 - row, col]])
+    }
+
+    fn downscale(&self, factor: usize) -> Self {
+        assert!(factor > 0, "Downscale factor must be non-zero");
+
+        let (h, w) = self.dim();
+        Array2::from_shape_fn((h.div_ceil(factor), w.div_ceil(factor)), |(row, col)| {
+            let block = self.slice(s![
+                row * factor..((row + 1) * factor).min(h),
+                col * factor..((col + 1) * factor).min(w)
+            ]);
+            let premultiplied = block.map(|pixel| premultiply(pixel.components()));
+            let mut sum = [T::zero(); N];
+            for components in &premultiplied {
+                for (total, &value) in sum.iter_mut().zip(components) {
+                    *total = *total + value;
+                }
+            }
+            let count: T = cast(block.len());
+            C::from_components(unpremultiply(sum.map(|total| total / count)))
+        })
+    }
+
+    fn resize(&self, size: [usize; 2], interpolation: Interpolation) -> Self {
+        let (h, w) = self.dim();
+        if h == 0 || w == 0 {
+            assert!(
+                size[0] == 0 || size[1] == 0,
+                "Cannot resize an empty image to a non-empty size"
+            );
+            return Array2::from_elem((size[0], size[1]), C::from_components([T::zero(); N]));
+        }
+
+        let half = cast::<T, _>(0.5);
+        let scale = [cast::<T, _>(h) / cast(size[0]), cast::<T, _>(w) / cast(size[1])];
+        Array2::from_shape_fn((size[0], size[1]), |(row, col)| {
+            let source = [
+                (cast::<T, _>(row) + half) * scale[0] - half,
+                (cast::<T, _>(col) + half) * scale[1] - half,
+            ];
+            sample(self, source, interpolation).expect("Output pixel centres map inside the image")
+        })
+    }
+
+    fn fit_into(&self, canvas: [usize; 2], background: C) -> Self {
+        let (h, w) = self.dim();
+        let mut out = Array2::from_elem((canvas[0], canvas[1]), background);
+        if h == 0 || w == 0 || canvas[0] == 0 || canvas[1] == 0 {
+            return out;
+        }
+
+        let scale = (cast::<T, _>(canvas[0]) / cast(h)).min(cast::<T, _>(canvas[1]) / cast(w));
+        let fit = |len: usize, limit: usize| (cast::<T, _>(len) * scale).round().to_usize().unwrap_or(1).clamp(1, limit);
+        let size = [fit(h, canvas[0]), fit(w, canvas[1])];
+        let resized = self.resize(size, Interpolation::Bilinear);
+        out.paste(
+            &resized,
+            [((canvas[0] - size[0]) / 2) as isize, ((canvas[1] - size[1]) / 2) as isize],
+        );
+        out
+    }
+
+    fn find_vertical_seam(&self) -> Vec<usize> {
+        let (h, w) = self.dim();
+        assert!(h > 0 && w > 0, "Image must not be empty");
+
+        // Cumulative minimum energy of any seam ending at each pixel
+        let mut cost = self.sobel();
+        for row in 1..h {
+            for col in 0..w {
+                let above = (col.saturating_sub(1)..(col + 2).min(w))
+                    .map(|c| cost[[row - 1, c]])
+                    .fold(T::infinity(), T::min);
+                cost[[row, col]] = cost[[row, col]] + above;
+            }
+        }
+
+        // Trace back from the cheapest bottom pixel
+        let cheapest = |row: usize, columns: std::ops::Range<usize>| {
+            let start = columns.start;
+            columns.fold(start, |best, c| if cost[[row, c]] < cost[[row, best]] { c } else { best })
+        };
+        let mut seam = vec![cheapest(h - 1, 0..w); h];
+        for row in (0..h - 1).rev() {
+            let col = seam[row + 1];
+            seam[row] = cheapest(row, col.saturating_sub(1)..(col + 2).min(w));
+        }
+        seam
+    }
+
+    fn carve_seams(&self, remove_columns: usize) -> Result<Self, ImageError> {
+        let (h, w) = self.dim();
+        if remove_columns > w {
+            return Err(ImageError::TooManySeams(remove_columns));
+        }
+        if remove_columns == w || h == 0 {
+            return Ok(Array2::from_elem((h, w - remove_columns), C::from_components([T::zero(); N])));
+        }
+
+        let mut image = self.clone();
+        for _ in 0..remove_columns {
+            let seam = image.find_vertical_seam();
+            image = Array2::from_shape_fn((h, image.ncols() - 1), |(row, col)| {
+                image[[row, if col < seam[row] { col } else { col + 1 }]]
+            });
+        }
+        Ok(image)
+    }
+
+    fn warp_affine(&self, matrix: [[T; 3]; 2], output_size: [usize; 2], fill: C, interpolation: Interpolation) -> Self {
+        let [[a, b, tr], [c, d, tc]] = matrix;
+        let det = a * d - b * c;
+        assert!(det != T::zero() && det.is_finite(), "Affine matrix must be invertible");
+
+        // Inverse of the linear part, applied after removing the translation
+        let inverse = [[d / det, -b / det], [-c / det, a / det]];
+        Array2::from_shape_fn((output_size[0], output_size[1]), |(row, col)| {
+            let (y, x) = (cast::<T, _>(row) - tr, cast::<T, _>(col) - tc);
+            let source = [inverse[0][0] * y + inverse[0][1] * x, inverse[1][0] * y + inverse[1][1] * x];
+            sample(self, source, interpolation).unwrap_or(fill)
+        })
+    }
+
+    fn warp_perspective(&self, matrix: [[T; 3]; 3], output_size: [usize; 2], fill: C, interpolation: Interpolation) -> Self {
+        // Inverse by the adjugate, scaled by the determinant
+        let m = |i: usize, j: usize| matrix[i % 3][j % 3];
+        let cofactor = |i: usize, j: usize| m(i + 1, j + 1) * m(i + 2, j + 2) - m(i + 1, j + 2) * m(i + 2, j + 1);
+        let det = (0..3).fold(T::zero(), |acc, j| acc + matrix[0][j] * cofactor(0, j));
+        assert!(det != T::zero() && det.is_finite(), "Homography must be invertible");
+        let inverse: [[T; 3]; 3] = std::array::from_fn(|i| std::array::from_fn(|j| cofactor(j, i) / det));
+
+        Array2::from_shape_fn((output_size[0], output_size[1]), |(row, col)| {
+            let point = [cast::<T, _>(row), cast::<T, _>(col), T::one()];
+            let [y, x, w] = inverse.map(|r| r[0] * point[0] + r[1] * point[1] + r[2] * point[2]);
+            if w <= T::zero() {
+                return fill;
+            }
+            sample(self, [y / w, x / w], interpolation).unwrap_or(fill)
+        })
+    }
+
+    fn content_bounds(&self) -> Option<([usize; 2], [usize; 2])> {
+        bounds_where(self, |pixel| !has_alpha(N) || pixel.components()[N - 1] > T::zero())
+    }
+
+    fn content_bounds_colour(&self, background: C) -> Option<([usize; 2], [usize; 2])> {
+        let background = background.components();
+        bounds_where(self, |pixel| pixel.components() != background)
+    }
+
+    fn autocrop(&self) -> Self {
+        match self.content_bounds() {
+            Some(([row, col], [h, w])) => self.slice(s![row..row + h, col..col + w]).to_owned(),
+            None => Array2::from_shape_vec((0, 0), Vec::new()).expect("Empty shape matches empty data"),
+        }
+    }
+
+    fn suggest_crops(&self, aspect: T, count: usize) -> Vec<([usize; 2], [usize; 2])> {
+        assert!(aspect > T::zero(), "Aspect ratio must be positive");
+        let (h, w) = self.dim();
+        if h == 0 || w == 0 || count == 0 {
+            return Vec::new();
+        }
+
+        // Largest fitting rectangle of the aspect, halved
+        let (fit_h, fit_w) = if cast::<T, _>(w) > cast::<T, _>(h) * aspect {
+            (h, (cast::<T, _>(h) * aspect).round().to_usize().unwrap_or(1))
+        } else {
+            ((cast::<T, _>(w) / aspect).round().to_usize().unwrap_or(1), w)
+        };
+        let size = [(fit_h / 2).clamp(1, h), (fit_w / 2).clamp(1, w)];
+
+        // Total saliency of every crop position, from an integral image
+        let saliency = self.saliency();
+        let mut integral = Array2::from_elem((h + 1, w + 1), T::zero());
+        for ((row, col), &value) in saliency.indexed_iter() {
+            integral[[row + 1, col + 1]] = value + integral[[row, col + 1]] + integral[[row + 1, col]] - integral[[row, col]];
+        }
+        let mut candidates = Vec::with_capacity((h - size[0] + 1) * (w - size[1] + 1));
+        for row in 0..=h - size[0] {
+            for col in 0..=w - size[1] {
+                let (r1, c1) = (row + size[0], col + size[1]);
+                let total = integral[[r1, c1]] - integral[[row, c1]] - integral[[r1, col]] + integral[[row, col]];
+                candidates.push(([row, col], total));
+            }
+        }
+        candidates.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        // Greedily take the best crops that do not overlap those already chosen
+        let mut crops: Vec<([usize; 2], [usize; 2])> = Vec::with_capacity(count);
+        for (start, _) in candidates {
+            let overlaps = crops.iter().any(|(other, _)| {
+                start[0] < other[0] + size[0]
+                    && other[0] < start[0] + size[0]
+                    && start[1] < other[1] + size[1]
+                    && other[1] < start[1] + size[1]
+            });
+            if !overlaps {
+                crops.push((start, size));
+                if crops.len() == count {
+                    break;
+                }
+            }
+        }
+        crops
+    }
+
+    fn extract_components(&self, alpha_threshold: u8) -> Vec<Self> {
+        let (h, w) = self.dim();
+        let opaque = self.map(|pixel| !has_alpha(N) || pixel.to_bytes()[N - 1] > alpha_threshold);
+        let transparent = C::from_components([T::zero(); N]);
+
+        // Gather the members of each 8-connected shape, then copy them into their bounding boxes
+        let (labels, count) = label_components(&opaque, Connectivity::Eight);
+        let mut members = vec![Vec::new(); count];
+        for ((row, col), &label) in labels.indexed_iter() {
+            if label != 0 {
+                members[label as usize - 1].push((row, col));
+            }
+        }
+
+        let mut sprites = Vec::with_capacity(count);
+        for members in members {
+            let (min_row, max_row) = members.iter().fold((h, 0), |(lo, hi), &(r, _)| (lo.min(r), hi.max(r)));
+            let (min_col, max_col) = members.iter().fold((w, 0), |(lo, hi), &(_, c)| (lo.min(c), hi.max(c)));
+            let mut sprite = Array2::from_elem((max_row - min_row + 1, max_col - min_col + 1), transparent);
+            for (r, c) in members {
+                sprite[[r - min_row, c - min_col]] = self[[r, c]];
+            }
+            sprites.push(sprite);
+        }
+
+        sprites
+    }
+
+    fn symmetry_variants(&self) -> [Self; 8] {
+        let flipped = self.flip_horizontal();
+        [
+            self.clone(),
+            self.rotate_clockwise(),
+            self.rotate_half(),
+            self.rotate_anticlockwise(),
+            flipped.clone(),
+            flipped.rotate_clockwise(),
+            flipped.rotate_half(),
+            flipped.rotate_anticlockwise(),
+        ]
+    }
+
+    fn unique_symmetry_variants(&self) -> Vec<(Self, usize)> {
+        let mut unique: Vec<(Self, usize)> = Vec::with_capacity(8);
+        for variant in self.symmetry_variants() {
+            match unique.iter_mut().find(|(existing, _)| same_pixels(existing, &variant)) {
+                Some((_, count)) => *count += 1,
+                None => unique.push((variant, 1)),
+            }
+        }
+        unique
+    }
+}
+
+/// Helper function to find the bounding region, as `([row, column], [height, width])`, of pixels matching a predicate.
+fn bounds_where<C, F>(image: &Array2<C>, predicate: F) -> Option<([usize; 2], [usize; 2])>
+where
+    F: Fn(&C) -> bool,
+{
+    let (mut min_row, mut min_col, mut max_row, mut max_col) = (usize::MAX, usize::MAX, 0, 0);
+    for ((row, col), pixel) in image.indexed_iter() {
+        if predicate(pixel) {
+            min_row = min_row.min(row);
+            min_col = min_col.min(col);
+            max_row = max_row.max(row);
+            max_col = max_col.max(col);
+        }
+    }
+
+    (min_row != usize::MAX).then(|| ([min_row, min_col], [max_row - min_row + 1, max_col - min_col + 1]))
+}
+
+/// Helper function to sample an image at a fractional `[row, column]` position, with pixel centres on integer
+/// coordinates.
+///
+/// Returns `None` if the position lies outside the image.
+fn sample<C, T, const N: usize>(image: &Array2<C>, [sr, sc]: [T; 2], interpolation: Interpolation) -> Option<C>
+where
+    C: Components<T, N> + Copy,
+    T: Float + Send + Sync,
+{
+    let (h, w) = image.dim();
+    let half = cast::<T, _>(0.5);
+    if !(sr >= -half && sc >= -half && sr < cast::<T, _>(h) - half && sc < cast::<T, _>(w) - half) {
+        return None;
+    }
+
+    match interpolation {
+        Interpolation::Nearest => {
+            let r = sr.round().to_usize().unwrap_or(0).min(h - 1);
+            let c = sc.round().to_usize().unwrap_or(0).min(w - 1);
+            Some(image[[r, c]])
+        }
+        Interpolation::Bilinear => {
+            let (r0, c0) = (sr.floor(), sc.floor());
+            let (fr, fc) = (sr - r0, sc - c0);
+            let clamp = |i: T, len: usize| i.max(T::zero()).to_usize().unwrap_or(0).min(len - 1);
+            let mut out = [T::zero(); N];
+            for (dr, wr) in [(T::zero(), T::one() - fr), (T::one(), fr)] {
+                for (dc, wc) in [(T::zero(), T::one() - fc), (T::one(), fc)] {
+                    let pixel = premultiply(image[[clamp(r0 + dr, h), clamp(c0 + dc, w)]].components());
+                    for (o, v) in out.iter_mut().zip(pixel) {
+                        *o = *o + v * wr * wc;
+                    }
+                }
+            }
+            Some(C::from_components(unpremultiply(out)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chromatic::{Grey, GreyAlpha, Rgb, RgbAlpha};
+    use ndarray::{Array2, array, s};
+
+    use crate::{Components, ImageError, Interpolation, Transform};
+
+    fn grey(values: Array2<f32>) -> Array2<Grey<f32>> {
+        values.mapv(Grey::new)
+    }
+
+    fn values(image: &Array2<Grey<f32>>) -> Array2<f32> {
+        image.map(|pixel| pixel.components()[0])
+    }
+
+    #[test]
+    fn symmetry_variants_are_the_eight_rotations_and_reflections() {
+        let image = grey(array![[0.1, 0.2, 0.3], [0.4, 0.5, 0.6]]);
+        let variants = image.symmetry_variants();
+
+        assert_eq!(values(&variants[1]), array![[0.4, 0.1], [0.5, 0.2], [0.6, 0.3]]);
+        assert_eq!(values(&variants[2]), array![[0.6, 0.5, 0.4], [0.3, 0.2, 0.1]]);
+        assert_eq!(values(&variants[4]), array![[0.3, 0.2, 0.1], [0.6, 0.5, 0.4]]);
+        assert_eq!(image.unique_symmetry_variants().len(), 8);
+    }
+
+    #[test]
+    fn unique_symmetry_variants_count_multiplicities() {
+        let mirrored = grey(array![[0.2, 0.2], [0.8, 0.8]]);
+        let unique = mirrored.unique_symmetry_variants();
+        assert_eq!(unique.len(), 4);
+        assert!(unique.iter().all(|&(_, count)| count == 2));
+
+        let flat = grey(Array2::from_elem((2, 2), 0.5));
+        let unique = flat.unique_symmetry_variants();
+        assert_eq!(unique.len(), 1);
+        assert_eq!(unique[0].1, 8);
+    }
+
+    #[test]
+    fn content_bounds_trim_transparent_margins() {
+        let mut image = Array2::from_elem((4, 5), GreyAlpha::new(1.0_f32, 0.0));
+        image[[1, 1]] = GreyAlpha::new(0.3, 0.5);
+        image[[2, 3]] = GreyAlpha::new(0.7, 1.0);
+
+        assert_eq!(image.content_bounds(), Some(([1, 1], [2, 3])));
+        let cropped = image.autocrop();
+        assert_eq!(cropped.dim(), (2, 3));
+        assert_eq!(cropped[[1, 2]].components(), [0.7, 1.0]);
+
+        let empty = Array2::from_elem((3, 3), GreyAlpha::new(0.5_f32, 0.0));
+        assert_eq!(empty.content_bounds(), None);
+        assert_eq!(empty.autocrop().dim(), (0, 0));
+    }
+
+    #[test]
+    fn content_bounds_colour_trims_a_solid_background() {
+        let mut image = grey(Array2::from_elem((4, 4), 0.2));
+        image[[3, 2]] = Grey::new(0.9);
+
+        assert_eq!(image.content_bounds_colour(Grey::new(0.2)), Some(([3, 2], [1, 1])));
+        assert_eq!(image.content_bounds_colour(Grey::new(0.9)), Some(([0, 0], [4, 4])));
+        assert_eq!(grey(Array2::zeros((2, 2))).content_bounds_colour(Grey::new(0.0)), None);
+    }
+
+    #[test]
+    fn downscale_keeps_thin_lines_as_fractional_coverage() {
+        let mask = Array2::from_shape_fn((8, 8), |(row, col)| {
+            if row == col {
+                GreyAlpha::new(1.0_f32, 1.0)
+            } else {
+                GreyAlpha::new(0.0, 0.0)
+            }
+        });
+
+        let small = mask.downscale(4);
+        assert_eq!(small.dim(), (2, 2));
+        assert_eq!(small[[0, 0]].components(), [1.0, 0.25]);
+        assert_eq!(small[[1, 1]].components(), [1.0, 0.25]);
+        assert_eq!(small[[0, 1]].components()[1], 0.0);
+
+        assert_eq!(mask.slice(s![..5, ..5]).to_owned().downscale(2).dim(), (3, 3));
+    }
+
+    #[test]
+    fn extract_components_splits_separate_shapes() {
+        let mut sheet = Array2::from_elem((8, 10), GreyAlpha::new(0.0_f32, 0.0));
+        sheet.slice_mut(s![1..3, 1..4]).fill(GreyAlpha::new(0.3, 1.0));
+        sheet.slice_mut(s![0..4, 6..8]).fill(GreyAlpha::new(0.6, 1.0));
+        sheet.slice_mut(s![5..8, 2..3]).fill(GreyAlpha::new(0.9, 1.0));
+        sheet[[6, 3]] = GreyAlpha::new(0.9, 1.0);
+        sheet[[7, 9]] = GreyAlpha::new(1.0, 0.01);
+
+        let sprites = sheet.extract_components(10);
+        let sizes = sprites.iter().map(Array2::dim).collect::<Vec<_>>();
+        assert_eq!(sizes, [(4, 2), (2, 3), (3, 2)]);
+        assert_eq!(sprites[1][[0, 0]].components(), [0.3, 1.0]);
+        assert_eq!(sprites[2][[0, 1]].components()[1], 0.0);
+    }
+
+    #[test]
+    fn top_crop_suggestion_contains_the_subject() {
+        let mut image = Array2::from_elem((30, 40), Rgb::new(0.4_f32, 0.5, 0.6));
+        image.slice_mut(s![18..22, 28..32]).fill(Rgb::new(1.0, 0.1, 0.0));
+
+        let crops = image.suggest_crops(2.0, 3);
+        assert!(!crops.is_empty() && crops.len() <= 3);
+        let ([row, col], [height, width]) = crops[0];
+        assert_eq!([height, width], [10, 20]);
+        assert!(row <= 18 && row + height >= 22 && col <= 28 && col + width >= 32);
+
+        // Suggestions never overlap each other
+        for (i, &([r0, c0], [h0, w0])) in crops.iter().enumerate() {
+            for &([r1, c1], [h1, w1]) in &crops[i + 1..] {
+                assert!(r0 + h0 <= r1 || r1 + h1 <= r0 || c0 + w0 <= c1 || c1 + w1 <= c0);
+            }
+        }
+    }
+
+    #[test]
+    fn warp_affine_maps_input_positions_to_output_positions() {
+        let image = grey(Array2::from_shape_fn((3, 4), |(row, col)| (row * 4 + col) as f32 / 16.0));
+        let fill = Grey::new(1.0);
+
+        let shifted = image.warp_affine([[1.0, 0.0, 1.0], [0.0, 1.0, 2.0]], [3, 4], fill, Interpolation::Nearest);
+        assert_eq!(shifted[[2, 3]].components(), image[[1, 1]].components());
+        assert_eq!(shifted[[0, 3]].components(), [1.0]);
+        assert_eq!(shifted[[2, 1]].components(), [1.0]);
+
+        let doubled = image.warp_affine([[2.0, 0.0, 0.0], [0.0, 2.0, 0.0]], [5, 7], fill, Interpolation::Bilinear);
+        assert_eq!(doubled[[2, 4]].components(), image[[1, 2]].components());
+        let mean = (0.0 + 1.0 + 4.0 + 5.0) / 4.0 / 16.0;
+        assert!((doubled[[1, 1]].components()[0] - mean).abs() < 1e-6);
+    }
+
+    #[test]
+    fn warp_perspective_divides_by_w_and_fills_behind_the_plane() {
+        let image = grey(Array2::from_shape_fn((3, 4), |(row, col)| (row * 4 + col) as f32 / 16.0));
+        let fill = Grey::new(1.0);
+        let shift = [[1.0, 0.0, 1.0], [0.0, 1.0, 2.0]];
+
+        // Scaling the whole homography changes nothing after the divide
+        let scaled = [[3.0, 0.0, 3.0], [0.0, 3.0, 6.0], [0.0, 0.0, 3.0]];
+        let warped = image.warp_perspective(scaled, [3, 4], fill, Interpolation::Nearest);
+        let affine = image.warp_affine(shift, [3, 4], fill, Interpolation::Nearest);
+        assert!(warped.iter().zip(&affine).all(|(a, b)| a.components() == b.components()));
+
+        let behind = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, -1.0]];
+        let warped = image.warp_perspective(behind, [3, 4], fill, Interpolation::Bilinear);
+        assert!(warped.iter().all(|pixel| pixel.components() == [1.0]));
+
+        // A projective map compressing columns further from the origin
+        let keystone = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.5, 1.0]];
+        let warped = image.warp_perspective(keystone, [3, 4], fill, Interpolation::Nearest);
+        assert_eq!(warped[[0, 1]].components(), image[[0, 2]].components());
+    }
+
+    #[test]
+    fn resize_does_not_darken_opaque_colour_next_to_transparency() {
+        let image = Array2::from_shape_fn((4, 4), |(_, col)| {
+            if col < 2 {
+                RgbAlpha::new(0.0_f32, 0.0, 0.0, 0.0)
+            } else {
+                RgbAlpha::new(1.0, 0.8, 0.2, 1.0)
+            }
+        });
+
+        let resized = image.resize([8, 12], Interpolation::Bilinear);
+        assert_eq!(resized.dim(), (8, 12));
+        for col in 0..12 {
+            let [red, green, blue, alpha] = resized[[4, col]].components();
+            if alpha > 0.0 {
+                assert!((red - 1.0).abs() < 1e-6 && (green - 0.8).abs() < 1e-6 && (blue - 0.2).abs() < 1e-6);
+            }
+        }
+        let alpha = |col: usize| resized[[4, col]].components()[3];
+        assert!(alpha(0) == 0.0 && alpha(5) > 0.0 && alpha(5) < 1.0 && alpha(11) == 1.0);
+    }
+
+    #[test]
+    fn fit_into_letterboxes_wide_images() {
+        let image = Array2::from_elem((10, 40), Rgb::new(1.0_f32, 0.0, 0.0));
+        let fitted = image.fit_into([20, 20], Rgb::new(0.0, 0.0, 1.0));
+        assert_eq!(fitted.dim(), (20, 20));
+
+        let is_image = fitted.map(|pixel| pixel.components() == [1.0, 0.0, 0.0]);
+        let rows = (0..20)
+            .filter(|&row| is_image.row(row).iter().all(|&on| on))
+            .collect::<Vec<_>>();
+        assert_eq!(rows, (7..12).collect::<Vec<_>>());
+        assert_eq!(is_image.iter().filter(|&&on| on).count(), 5 * 20);
+    }
+
+    #[test]
+    fn seams_avoid_detail_and_carving_narrows_the_image() {
+        // Flat background with steep ramps on the left and a bright stripe on the right
+        let image = grey(Array2::from_shape_fn((6, 10), |(_, col)| match col {
+            0 => 0.0,
+            1 => 0.3,
+            2 => 0.9,
+            8 => 1.0,
+            _ => 0.5,
+        }));
+
+        let seam = image.find_vertical_seam();
+        assert_eq!(seam.len(), 6);
+        assert!(seam.windows(2).all(|pair| pair[0].abs_diff(pair[1]) <= 1));
+        assert!(seam.iter().all(|&col| (4..=6).contains(&col)), "{seam:?}");
+
+        let carved = image.carve_seams(3).unwrap();
+        assert_eq!(carved.dim(), (6, 7));
+        assert!(carved.row(0).iter().any(|pixel| pixel.components() == [1.0]));
+        assert!(matches!(image.carve_seams(11), Err(ImageError::TooManySeams(11))));
+    }
+}
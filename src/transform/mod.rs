@@ -0,0 +1,128 @@
+use ndarray::Array2;
+use num_traits::Float;
+
+use crate::{Components, ImageError};
+
+mod arr2;
+
+/// How source pixels are sampled at fractional positions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Take the nearest pixel.
+    Nearest,
+    /// Blend the four surrounding pixels by their proximity.
+    Bilinear,
+}
+
+/// Trait for geometric transformations of an image.
+pub trait Transform<C, T, const N: usize>
+where
+    C: Components<T, N> + Clone,
+    T: Float + Send + Sync,
+{
+    /// Rotate the image a quarter turn clockwise.
+    fn rotate_clockwise(&self) -> Array2<C>;
+
+    /// Rotate the image a quarter turn anticlockwise.
+    fn rotate_anticlockwise(&self) -> Array2<C>;
+
+    /// Rotate the image a half turn.
+    fn rotate_half(&self) -> Array2<C>;
+
+    /// Mirror the image left-to-right.
+    fn flip_horizontal(&self) -> Array2<C>;
+
+    /// Mirror the image top-to-bottom.
+    fn flip_vertical(&self) -> Array2<C>;
+
+    /// Shrink the image by an integer factor, averaging each `factor x factor` block of pixels.
+    ///
+    /// Alpha is averaged directly, so partial coverage such as thin anti-aliased lines is kept as fractional alpha, and
+    /// colour is averaged weighted by alpha. Blocks at the right and bottom edges may be partial.
+    fn downscale(&self, factor: usize) -> Array2<C>;
+
+    /// Resize the image to the given `[height, width]`, sampling the image at each output pixel centre.
+    ///
+    /// Bilinear sampling interpolates premultiplied colour, so transparent pixels do not bleed their colour into opaque
+    /// neighbours.
+    fn resize(&self, size: [usize; 2], interpolation: Interpolation) -> Array2<C>;
+
+    /// Resize the image to fit within a canvas of the given `[height, width]`, preserving its aspect ratio, and centre
+    /// it on a canvas of exactly that size filled with the background colour.
+    ///
+    /// Resizing uses bilinear sampling, as in `resize`.
+    fn fit_into(&self, canvas: [usize; 2], background: C) -> Array2<C>;
+
+    /// Find the connected top-to-bottom path of least total Sobel gradient energy, as the column of the path in each row.
+    ///
+    /// Consecutive rows of the seam differ by at most one column. The image must not be empty.
+    fn find_vertical_seam(&self) -> Vec<usize>;
+
+    /// Narrow the image by content-aware seam carving, repeatedly removing the lowest-energy vertical seam.
+    ///
+    /// Energy is recomputed after each removal. Returns an error if more columns are requested than the image has.
+    fn carve_seams(&self, remove_columns: usize) -> Result<Array2<C>, ImageError>;
+
+    /// Warp the image by an affine transform into an image of the given `[height, width]`.
+    ///
+    /// The matrix maps input positions to output positions, `[row', column'] = M · [row, column, 1]`, with pixel centres
+    /// on integer coordinates. Each output pixel is mapped back through the inverse transform and sampled from the
+    /// image, and pixels falling outside it take the `fill` colour. Bilinear sampling blends colour weighted by alpha.
+    /// The matrix must be invertible.
+    fn warp_affine(&self, matrix: [[T; 3]; 2], output_size: [usize; 2], fill: C, interpolation: Interpolation) -> Array2<C>;
+
+    /// Warp the image by a projective transform (homography) into an image of the given `[height, width]`.
+    ///
+    /// The matrix maps homogeneous input positions to output positions, `[row' w, column' w, w] = H · [row, column, 1]`,
+    /// and each output pixel is mapped back through the inverse and divided by its homogeneous coordinate before
+    /// sampling as in `warp_affine`. Pixels mapping outside the image, or behind the image plane (`w <= 0`), take the
+    /// `fill` colour. The matrix must be invertible.
+    fn warp_perspective(
+        &self,
+        matrix: [[T; 3]; 3],
+        output_size: [usize; 2],
+        fill: C,
+        interpolation: Interpolation,
+    ) -> Array2<C>;
+
+    /// Find the smallest region containing every pixel with non-zero alpha, as `([row, column], [height, width])`.
+    ///
+    /// Colours without an alpha channel are treated as opaque, so the whole image is returned.
+    /// Returns `None` if every pixel is fully transparent, or the image is empty.
+    fn content_bounds(&self) -> Option<([usize; 2], [usize; 2])>;
+
+    /// Find the smallest region containing every pixel whose components differ from the background colour.
+    ///
+    /// Returns `None` if every pixel matches the background, or the image is empty.
+    fn content_bounds_colour(&self, background: C) -> Option<([usize; 2], [usize; 2])>;
+
+    /// Trim fully transparent margins from the image.
+    ///
+    /// A fully transparent image is cropped to an empty `0x0` image.
+    fn autocrop(&self) -> Array2<C>;
+
+    /// Suggest up to `count` non-overlapping crops of the given `aspect` (width over height), as
+    /// `([row, column], [height, width])`, ranked by their total saliency.
+    ///
+    /// Crops are half the linear size of the largest rectangle of that aspect fitting within the image.
+    fn suggest_crops(&self, aspect: T, count: usize) -> Vec<([usize; 2], [usize; 2])>;
+
+    /// Split the image into its separate opaque shapes, each trimmed to its bounding box.
+    ///
+    /// Pixels whose 8-bit alpha exceeds the threshold are opaque and are grouped with their 8-connected neighbours.
+    /// Shapes are returned in row-major order of their first pixel, and pixels of other shapes falling within a shape's
+    /// bounding box are cleared to transparent.
+    fn extract_components(&self, alpha_threshold: u8) -> Vec<Array2<C>>;
+
+    /// Generate the eight rotations and reflections of the image.
+    ///
+    /// The first four are the identity and successive clockwise quarter turns, the last four are the same turns of the
+    /// horizontally flipped image.
+    fn symmetry_variants(&self) -> [Array2<C>; 8];
+
+    /// Generate the distinct rotations and reflections of the image, paired with how many of the eight variants they
+    /// account for.
+    ///
+    /// Variants are the same when they have equal dimensions and every pixel has equal components.
+    fn unique_symmetry_variants(&self) -> Vec<(Array2<C>, usize)>;
+}